@@ -0,0 +1,262 @@
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::util::csv_field;
+use crate::{Uptobox, UptoboxPath, UptoboxResult, Walk, WalkFile};
+
+/// Download statistics of the files of a walk at a given time, to be saved and compared later
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StatsSnapshot {
+    /// Date of the snapshot, in seconds since the unix epoch
+    pub taken_at: u64,
+    pub files: Vec<FileStats>,
+}
+
+/// Statistics of a file of StatsSnapshot
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileStats {
+    /// The path of the folder of the file
+    pub path: UptoboxPath,
+    pub file_code: String,
+    pub file_name: String,
+    pub file_size: usize,
+    pub downloads: usize,
+    pub streams: usize,
+    pub last_download: String,
+    pub last_stream: String,
+}
+
+/// Statistics of the files of a folder, without its subfolders
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FolderStats {
+    pub path: UptoboxPath,
+    pub file_count: usize,
+    pub downloads: usize,
+    pub streams: usize,
+    pub never_downloaded: usize,
+}
+
+/// Evolution of the statistics of a file between two snapshots
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FileTrend {
+    pub path: UptoboxPath,
+    pub file_code: String,
+    pub file_name: String,
+
+    /// Downloads at the time of the newer snapshot
+    pub downloads: usize,
+
+    /// Downloads since the older snapshot, the files missing from the older snapshot start at 0
+    pub downloads_delta: i64,
+
+    /// Streams at the time of the newer snapshot
+    pub streams: usize,
+
+    /// Streams since the older snapshot, the files missing from the older snapshot start at 0
+    pub streams_delta: i64,
+}
+
+/// Evolution of the statistics between two snapshots
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct TrendReport {
+    /// Seconds between the two snapshots
+    pub elapsed: u64,
+
+    /// The files of the newer snapshot, from the most downloaded since the older snapshot
+    pub files: Vec<FileTrend>,
+}
+
+impl StatsSnapshot {
+    /// Take a snapshot of the files of a walk
+    pub fn from_files(files: Vec<WalkFile>) -> Self {
+        let taken_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let files = files
+            .into_iter()
+            .map(|f| FileStats {
+                path: f.path,
+                file_code: f.file.file_code,
+                file_name: f.file.file_name,
+                file_size: f.file.file_size,
+                downloads: f.file.file_downloads,
+                streams: f.file.nb_stream,
+                last_download: f.file.file_last_download,
+                last_stream: f.file.last_stream,
+            })
+            .collect();
+
+        Self { taken_at, files }
+    }
+
+    /// The `n` most downloaded files, from the most downloaded
+    pub fn most_downloaded(&self, n: usize) -> Vec<&FileStats> {
+        self.top(n, |f| f.downloads)
+    }
+
+    /// The `n` most streamed files, from the most streamed
+    pub fn most_streamed(&self, n: usize) -> Vec<&FileStats> {
+        self.top(n, |f| f.streams)
+    }
+
+    /// The files never downloaded
+    pub fn never_downloaded(&self) -> Vec<&FileStats> {
+        self.files.iter().filter(|f| f.downloads == 0).collect()
+    }
+
+    /// Statistics of each folder, from the most downloaded
+    pub fn downloads_per_folder(&self) -> Vec<FolderStats> {
+        let mut folders: BTreeMap<&UptoboxPath, FolderStats> = BTreeMap::new();
+        for file in &self.files {
+            let folder = folders.entry(&file.path).or_insert_with(|| FolderStats {
+                path: file.path.clone(),
+                file_count: 0,
+                downloads: 0,
+                streams: 0,
+                never_downloaded: 0,
+            });
+            folder.file_count += 1;
+            folder.downloads += file.downloads;
+            folder.streams += file.streams;
+            folder.never_downloaded += usize::from(file.downloads == 0);
+        }
+
+        let mut folders: Vec<FolderStats> = folders.into_values().collect();
+        folders.sort_by_key(|f| Reverse(f.downloads));
+        folders
+    }
+
+    /// Compare this snapshot with a newer one
+    pub fn trends(&self, newer: &StatsSnapshot) -> TrendReport {
+        let older: HashMap<&str, &FileStats> = self
+            .files
+            .iter()
+            .map(|f| (f.file_code.as_str(), f))
+            .collect();
+
+        let mut files: Vec<FileTrend> = newer
+            .files
+            .iter()
+            .map(|f| {
+                let (downloads, streams) = older
+                    .get(f.file_code.as_str())
+                    .map(|o| (o.downloads, o.streams))
+                    .unwrap_or_default();
+
+                FileTrend {
+                    path: f.path.clone(),
+                    file_code: f.file_code.clone(),
+                    file_name: f.file_name.clone(),
+                    downloads: f.downloads,
+                    downloads_delta: f.downloads as i64 - downloads as i64,
+                    streams: f.streams,
+                    streams_delta: f.streams as i64 - streams as i64,
+                }
+            })
+            .collect();
+        files.sort_by_key(|f| Reverse(f.downloads_delta));
+
+        TrendReport {
+            elapsed: newer.taken_at.saturating_sub(self.taken_at),
+            files,
+        }
+    }
+
+    /// Export the snapshot as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Read a snapshot exported as json
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Export the snapshot as csv, with one line per file
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("path,file_code,file_name,file_size,downloads,streams\n");
+
+        for file in &self.files {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(&file.path.to_string()),
+                csv_field(&file.file_code),
+                csv_field(&file.file_name),
+                file.file_size,
+                file.downloads,
+                file.streams,
+            ));
+        }
+
+        csv
+    }
+
+    /// Export the statistics of each folder as csv, with one line per folder
+    pub fn folders_to_csv(&self) -> String {
+        let mut csv = String::from("path,file_count,downloads,streams,never_downloaded\n");
+
+        for folder in self.downloads_per_folder() {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(&folder.path.to_string()),
+                folder.file_count,
+                folder.downloads,
+                folder.streams,
+                folder.never_downloaded,
+            ));
+        }
+
+        csv
+    }
+
+    fn top(&self, n: usize, key: impl Fn(&FileStats) -> usize) -> Vec<&FileStats> {
+        let mut files: Vec<&FileStats> = self.files.iter().filter(|f| key(f) > 0).collect();
+        files.sort_by_key(|f| Reverse(key(f)));
+        files.truncate(n);
+        files
+    }
+}
+
+impl TrendReport {
+    /// Export the report as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Export the report as csv, with one line per file
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "path,file_code,file_name,downloads,downloads_delta,streams,streams_delta\n",
+        );
+
+        for file in &self.files {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                csv_field(&file.path.to_string()),
+                csv_field(&file.file_code),
+                csv_field(&file.file_name),
+                file.downloads,
+                file.downloads_delta,
+                file.streams,
+                file.streams_delta,
+            ));
+        }
+
+        csv
+    }
+}
+
+/// Analytics
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and take a snapshot of the statistics of their files
+    pub async fn stats_snapshot(&self, walk: &Walk) -> UptoboxResult<StatsSnapshot> {
+        let files: Vec<WalkFile> = self.walk_files(walk).try_collect().await?;
+
+        Ok(StatsSnapshot::from_files(files))
+    }
+}
@@ -0,0 +1,217 @@
+use futures::TryStreamExt;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{Uptobox, UptoboxPath, UptoboxResult, Walk, WalkFolder};
+
+/// Disk usage of a folder and its subfolders
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DiskUsage {
+    pub path: UptoboxPath,
+
+    /// Size of the files of this folder only
+    pub own_size: usize,
+
+    /// Number of files of this folder only
+    pub own_file_count: usize,
+
+    /// Size of the files of this folder and its subfolders
+    pub total_size: usize,
+
+    /// Number of files of this folder and its subfolders
+    pub total_file_count: usize,
+
+    pub children: Vec<DiskUsage>,
+}
+
+/// Disk usage report
+#[derive(Serialize, Debug, Clone)]
+pub struct DiskUsageReport {
+    pub root: DiskUsage,
+
+    /// The largest files, from the largest
+    pub largest_files: Vec<DiskUsageFile>,
+}
+
+/// File of DiskUsageReport
+#[derive(Serialize, Debug, Clone)]
+pub struct DiskUsageFile {
+    pub path: UptoboxPath,
+    pub file_code: String,
+    pub file_name: String,
+    pub file_size: usize,
+}
+
+impl DiskUsageReport {
+    /// Build the report from the folders of a walk
+    ///
+    /// The first folder is the root of the report, `largest_files` is the number of files kept in `largest_files`
+    pub fn from_folders(folders: &[WalkFolder], largest_files: usize) -> Self {
+        let root_path = folders.first().map(|f| f.path.clone()).unwrap_or_default();
+
+        let mut usages = BTreeMap::new();
+        for folder in folders {
+            usages.insert(
+                folder.path.clone(),
+                DiskUsage {
+                    path: folder.path.clone(),
+                    own_size: folder.files.iter().map(|f| f.file_size).sum(),
+                    own_file_count: folder.files.len(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // The deepest folders are moved into their parents first
+        let mut paths: Vec<UptoboxPath> = usages.keys().cloned().collect();
+        paths.sort_by_key(|p| std::cmp::Reverse(p.depth()));
+
+        for path in paths {
+            let mut usage = usages.remove(&path).unwrap_or_default();
+            usage.total_size += usage.own_size;
+            usage.total_file_count += usage.own_file_count;
+
+            match path.parent().filter(|_| path != root_path) {
+                Some(parent) if usages.contains_key(&parent) => {
+                    let parent = usages.get_mut(&parent).unwrap();
+                    parent.total_size += usage.total_size;
+                    parent.total_file_count += usage.total_file_count;
+                    parent.children.push(usage);
+                }
+                _ => {
+                    usages.insert(path, usage);
+                }
+            }
+        }
+
+        let mut largest: Vec<DiskUsageFile> = folders
+            .iter()
+            .flat_map(|folder| {
+                folder.files.iter().map(|file| DiskUsageFile {
+                    path: folder.path.clone(),
+                    file_code: file.file_code.clone(),
+                    file_name: file.file_name.clone(),
+                    file_size: file.file_size,
+                })
+            })
+            .collect();
+        largest.sort_by_key(|f| std::cmp::Reverse(f.file_size));
+        largest.truncate(largest_files);
+
+        let mut root = usages.remove(&root_path).unwrap_or(DiskUsage {
+            path: root_path,
+            ..Default::default()
+        });
+        root.sort_by_size();
+
+        Self {
+            root,
+            largest_files: largest,
+        }
+    }
+
+    /// Export the report as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl DiskUsage {
+    /// Sort the subfolders recursively, from the largest
+    pub fn sort_by_size(&mut self) {
+        self.children
+            .sort_by(|a, b| b.total_size.cmp(&a.total_size).then(a.path.cmp(&b.path)));
+        self.children.iter_mut().for_each(DiskUsage::sort_by_size);
+    }
+
+    /// Sort the subfolders recursively, by name
+    pub fn sort_by_name(&mut self) {
+        self.children.sort_by(|a, b| a.path.cmp(&b.path));
+        self.children.iter_mut().for_each(DiskUsage::sort_by_name);
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, prefix: &str, last: bool) -> fmt::Result {
+        let name = match prefix.is_empty() {
+            true => self.path.to_string(),
+            false => self.path.file_name().unwrap_or_default().to_string(),
+        };
+        let branch = match (prefix.is_empty(), last) {
+            (true, _) => "",
+            (false, true) => "└── ",
+            (false, false) => "├── ",
+        };
+        writeln!(
+            f,
+            "{prefix}{branch}{name} {} ({} files)",
+            human_size(self.total_size),
+            self.total_file_count
+        )?;
+
+        let prefix = match (prefix.is_empty(), last) {
+            (true, _) => " ".to_string(),
+            (false, true) => format!("{prefix}    "),
+            (false, false) => format!("{prefix}│   "),
+        };
+        for (i, child) in self.children.iter().enumerate() {
+            child.fmt_tree(f, &prefix, i + 1 == self.children.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Human readable tree
+impl fmt::Display for DiskUsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.fmt_tree(f, "", true)?;
+
+        if !self.largest_files.is_empty() {
+            writeln!(f, "\nLargest files:")?;
+            for file in &self.largest_files {
+                writeln!(
+                    f,
+                    "{:>10}  {}/{}",
+                    human_size(file.file_size),
+                    file.path,
+                    file.file_name
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Disk usage
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and report their disk usage
+    ///
+    /// `largest_files` is the number of files kept in the list of the largest files
+    pub async fn disk_usage(
+        &self,
+        walk: &Walk,
+        largest_files: usize,
+    ) -> UptoboxResult<DiskUsageReport> {
+        let folders: Vec<WalkFolder> = self.walk(walk).try_collect().await?;
+
+        Ok(DiskUsageReport::from_folders(&folders, largest_files))
+    }
+}
+
+/// Format a size in bytes with a binary unit
+pub(crate) fn human_size(size: usize) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{size} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
@@ -0,0 +1,168 @@
+use futures::TryStreamExt;
+use std::collections::HashMap;
+
+use crate::util::BATCH_SIZE;
+use crate::{FindDuplicates, GetFilesFiles, Uptobox, UptoboxResult, Walk, WalkFile};
+
+/// Files with the same name, size and content hash
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub file_name: String,
+    pub file_size: usize,
+    pub content_hash: Option<String>,
+    pub files: Vec<WalkFile>,
+}
+
+/// Duplicate files of a walk
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateReport {
+    /// The groups, from the one wasting the most space
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// File kept in each group of duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepStrategy {
+    /// Keep the file created first, a file without a creation date is only kept if no file has one
+    Oldest,
+
+    /// Keep the file created last, a file without a creation date is only kept if no file has one
+    Newest,
+
+    /// Keep the file downloaded the most
+    MostDownloaded,
+}
+
+/// Files to keep and to delete to remove the duplicates
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionPlan {
+    pub keep: Vec<WalkFile>,
+    pub delete: Vec<WalkFile>,
+}
+
+impl DuplicateGroup {
+    /// Space used by the copies
+    pub fn wasted_space(&self) -> usize {
+        self.file_size * self.files.len().saturating_sub(1)
+    }
+}
+
+impl DuplicateReport {
+    /// Group the duplicate files
+    pub fn from_files(files: Vec<WalkFile>, find_duplicates: &FindDuplicates) -> Self {
+        let mut groups: HashMap<(String, usize, Option<String>), Vec<WalkFile>> = HashMap::new();
+        for file in files {
+            let content_hash = find_duplicates
+                .content_hashes
+                .get(&file.file.file_code)
+                .cloned();
+            let key = (
+                file.file.file_name.clone(),
+                file.file.file_size,
+                content_hash,
+            );
+            groups.entry(key).or_default().push(file);
+        }
+
+        let mut groups: Vec<DuplicateGroup> = groups
+            .into_iter()
+            .filter(|(_, files)| files.len() > 1)
+            .map(
+                |((file_name, file_size, content_hash), files)| DuplicateGroup {
+                    file_name,
+                    file_size,
+                    content_hash,
+                    files,
+                },
+            )
+            .collect();
+        groups.sort_by(|a, b| {
+            b.wasted_space()
+                .cmp(&a.wasted_space())
+                .then_with(|| a.file_name.cmp(&b.file_name))
+        });
+
+        Self { groups }
+    }
+
+    /// Space used by all the copies
+    pub fn wasted_space(&self) -> usize {
+        self.groups.iter().map(DuplicateGroup::wasted_space).sum()
+    }
+
+    /// Choose the file to keep in each group, the others are deleted
+    pub fn plan(&self, strategy: KeepStrategy) -> ResolutionPlan {
+        let mut plan = ResolutionPlan::default();
+
+        for group in &self.groups {
+            let mut files = group.files.clone();
+            files.sort_by(|a, b| {
+                let (a, b) = (&a.file, &b.file);
+                let (created_a, created_b) = (created(a), created(b));
+                match strategy {
+                    // The files without a creation date are never preferred
+                    KeepStrategy::Oldest => created_a
+                        .is_none()
+                        .cmp(&created_b.is_none())
+                        .then(created_a.cmp(&created_b)),
+                    KeepStrategy::Newest => created_a
+                        .is_none()
+                        .cmp(&created_b.is_none())
+                        .then(created_b.cmp(&created_a)),
+                    KeepStrategy::MostDownloaded => b.file_downloads.cmp(&a.file_downloads),
+                }
+                .then_with(|| a.file_code.cmp(&b.file_code))
+            });
+
+            let mut files = files.into_iter();
+            plan.keep.extend(files.next());
+            plan.delete.extend(files);
+        }
+
+        plan
+    }
+}
+
+impl ResolutionPlan {
+    /// Space freed by the plan
+    pub fn freed_space(&self) -> usize {
+        self.delete.iter().map(|f| f.file.file_size).sum()
+    }
+}
+
+/// The creation date of a file, `None` if it is empty or zero
+///
+/// The dates of the api (`2023-01-31 12:00:00`) are compared as strings
+fn created(file: &GetFilesFiles) -> Option<&str> {
+    let created = file.file_created.trim();
+    match created.is_empty() || created.starts_with("0000-00-00") {
+        true => None,
+        false => Some(created),
+    }
+}
+
+/// Duplicates
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and group the duplicate files
+    pub async fn find_duplicates(
+        &self,
+        walk: &Walk,
+        find_duplicates: &FindDuplicates,
+    ) -> UptoboxResult<DuplicateReport> {
+        let files: Vec<WalkFile> = self.walk_files(walk).try_collect().await?;
+
+        Ok(DuplicateReport::from_files(files, find_duplicates))
+    }
+
+    /// Delete the files of a resolution plan, and return the number of files deleted
+    pub async fn execute_resolution_plan(&self, plan: &ResolutionPlan) -> UptoboxResult<usize> {
+        let mut deleted = 0;
+        for batch in plan.delete.chunks(BATCH_SIZE) {
+            deleted += self
+                .delete_files(batch.iter().map(|f| f.file.file_code.as_str()))
+                .await?;
+        }
+
+        Ok(deleted)
+    }
+}
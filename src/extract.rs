@@ -0,0 +1,145 @@
+use std::io;
+use std::path::Path;
+
+use crate::file_code::{strip_host, HOSTS};
+use crate::{FileCode, GetFilesFromPublicFolder};
+
+/// Characters ending an url in a text
+const DELIMITERS: [char; 13] = [
+    '"', '\'', '<', '>', '(', ')', '[', ']', '{', '}', '|', '\\', '`',
+];
+
+/// Characters removed from the end of an url, as they usually end a sentence
+const TRAILING: [char; 5] = ['.', ',', ';', '!', '?'];
+
+/// Links found in a text
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtractedLinks {
+    /// The files, in order of appearance
+    pub files: Vec<FileCode>,
+
+    /// The public folders, in order of appearance
+    pub public_folders: Vec<PublicFolder>,
+}
+
+/// Reference to a public folder
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PublicFolder {
+    /// The folder id
+    pub folder: usize,
+
+    /// The folder hash
+    pub hash: String,
+}
+
+impl PublicFolder {
+    /// Parse a public folder url (eg. `https://uptobox.com/user_public?hash=abcdef&folder=123`)
+    pub fn parse(input: &str) -> Option<Self> {
+        let path = strip_host(input.trim())?;
+        let (path, query) = path.split_once('?')?;
+        if path.trim_end_matches('/') != "/user_public" {
+            return None;
+        }
+
+        let query = query.split('#').next().unwrap_or_default();
+        let mut folder = None;
+        let mut hash = None;
+        for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+            match key {
+                "folder" => folder = value.parse().ok(),
+                "hash" if !value.is_empty() => hash = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            folder: folder?,
+            hash: hash?,
+        })
+    }
+}
+
+impl From<&PublicFolder> for GetFilesFromPublicFolder {
+    fn from(public_folder: &PublicFolder) -> Self {
+        GetFilesFromPublicFolder::new(public_folder.folder, &public_folder.hash)
+    }
+}
+
+impl From<PublicFolder> for GetFilesFromPublicFolder {
+    fn from(public_folder: PublicFolder) -> Self {
+        GetFilesFromPublicFolder::new(public_folder.folder, public_folder.hash)
+    }
+}
+
+impl ExtractedLinks {
+    /// Add a file, or the password of an already known file
+    fn push_file(&mut self, file_code: FileCode) {
+        match self.files.iter_mut().find(|f| f.code() == file_code.code()) {
+            Some(known) => {
+                if let (None, Some(password)) = (known.password(), file_code.password()) {
+                    *known = known.clone().with_password(password);
+                }
+            }
+            None => self.files.push(file_code),
+        }
+    }
+
+    /// Add a public folder if it is not already known
+    fn push_public_folder(&mut self, public_folder: PublicFolder) {
+        if !self.public_folders.contains(&public_folder) {
+            self.public_folders.push(public_folder);
+        }
+    }
+}
+
+/// Extract the uptobox and uptostream links of a text or an html document
+///
+/// The links are deduplicated, and the passwords attached to them (`https://uptobox.com/code:password`) are kept
+pub fn extract_links(text: &str) -> ExtractedLinks {
+    let text = text
+        .replace("&amp;", "&")
+        .replace("&#58;", ":")
+        .replace("&#x3a;", ":");
+    let lowercase = text.to_ascii_lowercase();
+
+    let mut links = ExtractedLinks::default();
+    let mut position = 0;
+
+    while let Some((start, host)) = HOSTS
+        .iter()
+        .filter_map(|host| Some((lowercase[position..].find(host)? + position, host)))
+        .min_by_key(|(start, _)| *start)
+    {
+        let end = text[start..]
+            .find(|c: char| c.is_whitespace() || DELIMITERS.contains(&c))
+            .map_or(text.len(), |end| end + start);
+        position = start + host.len();
+
+        // The host must not be part of another domain
+        if lowercase[..start].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '-') {
+            continue;
+        }
+
+        let start = ["https://www.", "http://www.", "https://", "http://", "www."]
+            .iter()
+            .find(|prefix| lowercase[..start].ends_with(*prefix))
+            .map_or(start, |prefix| start - prefix.len());
+        let url = text[start..end].trim_end_matches(TRAILING);
+
+        if let Some(public_folder) = PublicFolder::parse(url) {
+            links.push_public_folder(public_folder);
+        } else if let Ok(file_code) = FileCode::parse(url) {
+            links.push_file(file_code);
+        }
+
+        position = end;
+    }
+
+    links
+}
+
+/// Extract the uptobox and uptostream links of a file
+pub fn extract_links_from_file(path: impl AsRef<Path>) -> io::Result<ExtractedLinks> {
+    let content = std::fs::read(path)?;
+    Ok(extract_links(&String::from_utf8_lossy(&content)))
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::util::REDACTED;
+use crate::{Error, UptoboxResult};
+
+/// Hosts serving the files of uptobox
+pub(crate) const HOSTS: [&str; 7] = [
+    "uptobox.com",
+    "uptobox.fr",
+    "uptobox.eu",
+    "uptobox.link",
+    "uptostream.com",
+    "uptostream.fr",
+    "uptostream.eu",
+];
+
+/// Code of a file, with its optional password
+///
+/// It can be parsed from a bare code (`abcdef123456`), a code with its password (`abcdef123456:password`)
+/// or an url (`https://uptobox.com/abcdef123456`, `https://uptostream.com/iframe/abcdef123456`)
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FileCode {
+    code: String,
+    password: Option<String>,
+}
+
+impl FileCode {
+    /// Parse a file code
+    pub fn parse(input: &str) -> UptoboxResult<Self> {
+        let input = input.trim();
+
+        // The password is not kept in the error, everything after the first `:` following the scheme is redacted
+        let invalid = || {
+            let start = input.find("://").map_or(0, |i| i + 3);
+            match input[start..].find(':') {
+                Some(i) => Error::InvalidFileCode(format!("{}:{REDACTED}", &input[..start + i])),
+                None => Error::InvalidFileCode(input.to_string()),
+            }
+        };
+
+        let code = match strip_host(input) {
+            Some(path) => {
+                let path = path.split(['?', '#']).next().unwrap_or_default();
+                let mut segments = path.split('/').filter(|s| !s.is_empty());
+                match segments.next() {
+                    Some("iframe") => segments.next(),
+                    segment => segment,
+                }
+                .ok_or_else(invalid)?
+            }
+            None if input.contains("://") => return Err(invalid()),
+            None => input,
+        };
+
+        let (code, password) = match code.split_once(':') {
+            Some((code, password)) if !password.is_empty() => (code, Some(password.to_string())),
+            Some((code, _)) => (code, None),
+            None => (code, None),
+        };
+
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            code: code.to_string(),
+            password,
+        })
+    }
+
+    /// Set the password
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// The code of the file
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The password of the file
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// The url of the file on uptobox
+    pub fn url(&self) -> String {
+        format!("https://uptobox.com/{}", self.code)
+    }
+
+    /// The `code:password` form expected by the api
+    pub(crate) fn with_password_query(&self) -> String {
+        match &self.password {
+            Some(password) => format!("{}:{password}", self.code),
+            None => self.code.clone(),
+        }
+    }
+}
+
+/// Strip the scheme and the host of an uptobox url, and return its path
+///
+/// The scheme and the host are matched case-insensitively
+pub(crate) fn strip_host(input: &str) -> Option<&str> {
+    let url = strip_prefix_ignore_case(input, "https://")
+        .or_else(|| strip_prefix_ignore_case(input, "http://"))
+        .unwrap_or(input);
+    let url = strip_prefix_ignore_case(url, "www.").unwrap_or(url);
+
+    HOSTS.iter().find_map(|host| {
+        let path = strip_prefix_ignore_case(url, host)?;
+        match path.is_empty() || path.starts_with(['/', '?', '#']) {
+            true => Some(path),
+            false => None,
+        }
+    })
+}
+
+fn strip_prefix_ignore_case<'a>(input: &'a str, prefix: &str) -> Option<&'a str> {
+    match input.get(..prefix.len()) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => Some(&input[prefix.len()..]),
+        _ => None,
+    }
+}
+
+/// Join file codes as expected by the api
+pub(crate) fn join_file_codes(
+    file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+    with_password: bool,
+) -> UptoboxResult<String> {
+    let file_codes = file_codes
+        .into_iter()
+        .map(|file_code| {
+            let file_code = file_code.into_file_code()?;
+            Ok(match with_password {
+                true => file_code.with_password_query(),
+                false => file_code.code,
+            })
+        })
+        .collect::<UptoboxResult<Vec<String>>>()?;
+
+    Ok(file_codes.join(","))
+}
+
+/// Remove the duplicate codes, keeping the first password found for each code
+pub(crate) fn dedup_file_codes(file_codes: Vec<FileCode>) -> Vec<FileCode> {
+    let mut unique: Vec<FileCode> = Vec::with_capacity(file_codes.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for file_code in file_codes {
+        match positions.get(file_code.code()) {
+            Some(&position) => {
+                let known = &mut unique[position];
+                if let (None, Some(password)) = (known.password(), file_code.password()) {
+                    *known = known.clone().with_password(password);
+                }
+            }
+            None => {
+                positions.insert(file_code.code().to_string(), unique.len());
+                unique.push(file_code);
+            }
+        }
+    }
+
+    unique
+}
+
+/// Conversion into a FileCode, implemented for strings and FileCode
+pub trait IntoFileCode {
+    fn into_file_code(self) -> UptoboxResult<FileCode>;
+}
+
+impl<T> IntoFileCode for T
+where
+    T: TryInto<FileCode>,
+    Error: From<T::Error>,
+{
+    fn into_file_code(self) -> UptoboxResult<FileCode> {
+        Ok(self.try_into()?)
+    }
+}
+
+impl FromStr for FileCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for FileCode {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for FileCode {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl TryFrom<&String> for FileCode {
+    type Error = Error;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<&FileCode> for FileCode {
+    fn from(value: &FileCode) -> Self {
+        value.clone()
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl fmt::Display for FileCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl fmt::Debug for FileCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileCode")
+            .field("code", &self.code)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+impl Serialize for FileCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for FileCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Self::parse(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_code(code: &str) -> FileCode {
+        FileCode::parse(code).unwrap()
+    }
+
+    #[test]
+    fn dedup() {
+        let file_codes = dedup_file_codes(vec![
+            file_code("aaaaaaaaaaaa"),
+            file_code("bbbbbbbbbbbb:first"),
+            file_code("aaaaaaaaaaaa:secret"),
+            file_code("bbbbbbbbbbbb:second"),
+        ]);
+
+        assert_eq!(
+            file_codes,
+            [
+                file_code("aaaaaaaaaaaa:secret"),
+                file_code("bbbbbbbbbbbb:first")
+            ]
+        );
+    }
+}
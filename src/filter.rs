@@ -0,0 +1,335 @@
+use futures::{future, Stream, TryStreamExt};
+use std::ops::{Bound, RangeBounds};
+
+use crate::{
+    FileVisibility, GetFilesFiles, TranscodingState, Uptobox, UptoboxResult, Walk, WalkFile,
+};
+
+type Range<T> = (Bound<T>, Bound<T>);
+
+/// Filter over the files of a listing or a walk
+///
+/// Every condition set must match
+#[derive(Debug, Clone, Default)]
+pub struct FileFilter {
+    size: Option<Range<usize>>,
+    downloads: Option<Range<usize>>,
+    streams: Option<Range<usize>>,
+    name_glob: Option<String>,
+    #[cfg(feature = "regex")]
+    name_regex: Option<regex::Regex>,
+    #[cfg(feature = "chrono")]
+    created: Option<Range<chrono::NaiveDateTime>>,
+    #[cfg(feature = "chrono")]
+    last_download: Option<Range<chrono::NaiveDateTime>>,
+    public: Option<bool>,
+    password: Option<bool>,
+    transcoded: Option<bool>,
+}
+
+impl FileFilter {
+    /// Create a new instance, matching every file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the range of the size of the files, in bytes
+    pub fn size(&mut self, size: impl RangeBounds<usize>) -> &mut Self {
+        let _ = self.size.insert(to_range(size));
+        self
+    }
+
+    /// Set the range of the number of downloads
+    pub fn downloads(&mut self, downloads: impl RangeBounds<usize>) -> &mut Self {
+        let _ = self.downloads.insert(to_range(downloads));
+        self
+    }
+
+    /// Set the range of the number of streams
+    pub fn streams(&mut self, streams: impl RangeBounds<usize>) -> &mut Self {
+        let _ = self.streams.insert(to_range(streams));
+        self
+    }
+
+    /// Set a glob the names must match, `*` matches any characters and `?` matches one character
+    pub fn name_glob(&mut self, glob: impl Into<String>) -> &mut Self {
+        let _ = self.name_glob.insert(glob.into());
+        self
+    }
+
+    /// Set a regex the names must match
+    #[cfg(feature = "regex")]
+    pub fn name_regex(&mut self, regex: regex::Regex) -> &mut Self {
+        let _ = self.name_regex.insert(regex);
+        self
+    }
+
+    /// Set the range of the creation date
+    #[cfg(feature = "chrono")]
+    pub fn created(&mut self, created: impl RangeBounds<chrono::NaiveDateTime>) -> &mut Self {
+        let _ = self.created.insert(to_range(created));
+        self
+    }
+
+    /// Set the range of the last download date, the files never downloaded do not match
+    #[cfg(feature = "chrono")]
+    pub fn last_download(
+        &mut self,
+        last_download: impl RangeBounds<chrono::NaiveDateTime>,
+    ) -> &mut Self {
+        let _ = self.last_download.insert(to_range(last_download));
+        self
+    }
+
+    /// Only match the public files, or the private files
+    pub fn public(&mut self, public: bool) -> &mut Self {
+        let _ = self.public.insert(public);
+        self
+    }
+
+    /// Only match the files with a password, or without a password
+    pub fn password(&mut self, password: bool) -> &mut Self {
+        let _ = self.password.insert(password);
+        self
+    }
+
+    /// Only match the transcoded files, or the files not transcoded
+    pub fn transcoded(&mut self, transcoded: bool) -> &mut Self {
+        let _ = self.transcoded.insert(transcoded);
+        self
+    }
+
+    /// Check if a file matches the filter
+    pub fn matches(&self, file: &GetFilesFiles) -> bool {
+        let in_range = |range: &Option<Range<usize>>, value: usize| {
+            range.as_ref().is_none_or(|r| r.contains(&value))
+        };
+        let is = |expected: Option<bool>, value: bool| expected.is_none_or(|e| e == value);
+
+        in_range(&self.size, file.file_size)
+            && in_range(&self.downloads, file.file_downloads)
+            && in_range(&self.streams, file.nb_stream)
+            && self
+                .name_glob
+                .as_ref()
+                .is_none_or(|glob| glob_match(glob, &file.file_name))
+            && self.matches_regex(file)
+            && self.matches_dates(file)
+            && is(self.public, file.file_public == FileVisibility::Public)
+            && is(self.password, file.has_password())
+            && is(
+                self.transcoded,
+                file.transcoded == TranscodingState::Transcoded,
+            )
+    }
+
+    /// Keep the files of a stream matching the filter
+    pub fn apply<'a, S>(&'a self, stream: S) -> impl Stream<Item = UptoboxResult<WalkFile>> + 'a
+    where
+        S: Stream<Item = UptoboxResult<WalkFile>> + 'a,
+    {
+        stream.try_filter(move |file| future::ready(self.matches(&file.file)))
+    }
+
+    #[cfg(feature = "regex")]
+    fn matches_regex(&self, file: &GetFilesFiles) -> bool {
+        self.name_regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(&file.file_name))
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn matches_regex(&self, _: &GetFilesFiles) -> bool {
+        true
+    }
+
+    #[cfg(feature = "chrono")]
+    fn matches_dates(&self, file: &GetFilesFiles) -> bool {
+        let in_range = |range: &Option<Range<chrono::NaiveDateTime>>,
+                        value: Option<chrono::NaiveDateTime>| match (
+            range, value,
+        ) {
+            (None, _) => true,
+            (Some(range), Some(value)) => range.contains(&value),
+            (Some(_), None) => false,
+        };
+
+        in_range(&self.created, file.file_created_at())
+            && in_range(&self.last_download, file.file_last_download_at())
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    fn matches_dates(&self, _: &GetFilesFiles) -> bool {
+        true
+    }
+}
+
+/// Filter
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and return the files matching the filter
+    pub fn find_files<'a>(
+        &'a self,
+        walk: &Walk,
+        filter: &'a FileFilter,
+    ) -> impl Stream<Item = UptoboxResult<WalkFile>> + 'a {
+        filter.apply(self.walk_files(walk))
+    }
+}
+
+fn to_range<T: Clone>(range: impl RangeBounds<T>) -> Range<T> {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// Match a glob, `*` matches any characters and `?` matches one character
+fn glob_match(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut g, mut n) = (0, 0);
+    // Position of the last `*` in the glob, and of the name when it was reached
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_g, star_n)) => {
+                    g = star_g + 1;
+                    n = star_n + 1;
+                    star = Some((star_g, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Map;
+
+    fn file(name: &str) -> GetFilesFiles {
+        GetFilesFiles {
+            file_code: "abcdef123456".into(),
+            file_created: "2023-01-31 12:00:00".into(),
+            file_descr: String::new(),
+            file_downloads: 5,
+            file_last_download: String::new(),
+            file_name: name.into(),
+            file_password: None,
+            file_public: FileVisibility::Private,
+            file_size: 100,
+            id: None,
+            last_stream: String::new(),
+            nb_stream: 2,
+            transcoded: TranscodingState::NotTranscoded,
+            extra: Map::new(),
+        }
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("*.mkv", "movie.mkv"));
+        assert!(glob_match("*.mkv", ".mkv"));
+        assert!(!glob_match("*.mkv", "movie.mkv.part"));
+        assert!(glob_match("movie.???", "movie.mkv"));
+        assert!(!glob_match("movie.???", "movie.mp4a"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("**", "anything"));
+        assert!(!glob_match("", "a"));
+        assert!(glob_match("", ""));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(glob_match("*ab", "aab"));
+        assert!(glob_match("é?*", "été"));
+        // The match is case-sensitive
+        assert!(!glob_match("*.MKV", "movie.mkv"));
+    }
+
+    #[test]
+    fn ranges() {
+        let file = file("movie.mkv");
+        let matches = |filter: &mut FileFilter| filter.matches(&file);
+
+        assert!(matches(&mut FileFilter::new()));
+        assert!(matches(FileFilter::new().size(100..=100)));
+        assert!(matches(FileFilter::new().size(..101)));
+        assert!(!matches(FileFilter::new().size(..100)));
+        assert!(!matches(FileFilter::new().size(101..)));
+        assert!(matches(FileFilter::new().downloads(5..)));
+        assert!(!matches(FileFilter::new().downloads(0..5)));
+        assert!(matches(FileFilter::new().streams(2..3)));
+        assert!(!matches(FileFilter::new().streams(3..)));
+        // Every condition must match
+        assert!(!matches(FileFilter::new().size(..).downloads(6..)));
+    }
+
+    #[test]
+    fn flags() {
+        let private = file("movie.mkv");
+        let mut public = file("movie.mkv");
+        public.file_public = FileVisibility::Public;
+        public.file_password = Some("secret".into());
+        public.transcoded = TranscodingState::Transcoded;
+
+        for (filter, expected) in [
+            (FileFilter::new().public(true).clone(), [false, true]),
+            (FileFilter::new().public(false).clone(), [true, false]),
+            (FileFilter::new().password(true).clone(), [false, true]),
+            (FileFilter::new().password(false).clone(), [true, false]),
+            (FileFilter::new().transcoded(true).clone(), [false, true]),
+            (FileFilter::new().transcoded(false).clone(), [true, false]),
+        ] {
+            assert_eq!(
+                [filter.matches(&private), filter.matches(&public)],
+                expected,
+                "{filter:?}"
+            );
+        }
+
+        let mut unknown = file("movie.mkv");
+        unknown.transcoded = TranscodingState::Unknown(2);
+        assert!(!FileFilter::new().transcoded(true).matches(&unknown));
+    }
+
+    #[test]
+    fn name_glob() {
+        let filter = FileFilter::new().name_glob("*.mkv").clone();
+
+        assert!(filter.matches(&file("movie.mkv")));
+        assert!(!filter.matches(&file("movie.mp4")));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn dates() {
+        let date = |d: &str| crate::model::timestamp::parse_opt(d).unwrap();
+        let mut file = file("movie.mkv");
+        file.file_last_download = "2023-02-01 00:00:00".into();
+        let matches = |filter: &mut FileFilter| filter.matches(&file);
+
+        assert!(matches(
+            FileFilter::new().created(date("2023-01-31 12:00:00")..)
+        ));
+        assert!(!matches(
+            FileFilter::new().created(..date("2023-01-31 12:00:00"))
+        ));
+        assert!(matches(
+            FileFilter::new().last_download(..=date("2023-02-01 00:00:00"))
+        ));
+
+        // The files never downloaded do not match a last download range
+        file.file_last_download = "0000-00-00 00:00:00".into();
+        assert!(!FileFilter::new().last_download(..).matches(&file));
+    }
+}
@@ -0,0 +1,37 @@
+/// Input
+#[derive(Debug, Clone)]
+pub struct CheckLinks {
+    /// Number of file codes sent in each request
+    pub(crate) chunk_size: usize,
+
+    /// Number of requests running at the same time
+    pub(crate) concurrency: usize,
+}
+
+impl CheckLinks {
+    /// Create a new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of file codes sent in each request
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set the number of requests running at the same time
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+impl Default for CheckLinks {
+    fn default() -> Self {
+        Self {
+            chunk_size: 100,
+            concurrency: 4,
+        }
+    }
+}
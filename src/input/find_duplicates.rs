@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use crate::FileCode;
+
+/// Input
+#[derive(Debug, Clone, Default)]
+pub struct FindDuplicates {
+    /// Hash of the content of the files, by file code
+    pub(crate) content_hashes: HashMap<String, String>,
+}
+
+impl FindDuplicates {
+    /// Create a new instance, grouping the files by name and size
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the hash of the content of a file
+    ///
+    /// The files with a hash are also grouped by hash
+    pub fn content_hash(&mut self, file_code: &FileCode, hash: impl Into<String>) -> &mut Self {
+        self.content_hashes
+            .insert(file_code.code().to_string(), hash.into());
+        self
+    }
+
+    /// Set the hashes of the content of the files, by file code
+    pub fn content_hashes(
+        &mut self,
+        content_hashes: impl IntoIterator<Item = (String, String)>,
+    ) -> &mut Self {
+        self.content_hashes.extend(content_hashes);
+        self
+    }
+}
@@ -1,7 +1,11 @@
 use serde::Serialize;
+use std::fmt;
+
+use crate::util::REDACTED;
+use crate::{FileCode, IntoFileCode, UptoboxResult};
 
 /// Input
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Default)]
 pub struct GetDownloadUrl {
     /// The file code
     file_code: String,
@@ -11,12 +15,9 @@ pub struct GetDownloadUrl {
 }
 
 impl GetDownloadUrl {
-    /// Create a new instance
-    pub fn new(file_code: impl Into<String>) -> Self {
-        Self {
-            file_code: file_code.into(),
-            ..Default::default()
-        }
+    /// Create a new instance, the password of the file code is kept
+    pub fn new(file_code: impl IntoFileCode) -> UptoboxResult<Self> {
+        Ok(file_code.into_file_code()?.into())
     }
 
     /// Set the password
@@ -25,3 +26,27 @@ impl GetDownloadUrl {
         self
     }
 }
+
+impl From<FileCode> for GetDownloadUrl {
+    fn from(file_code: FileCode) -> Self {
+        Self {
+            password: file_code.password().unwrap_or_default().to_string(),
+            file_code: file_code.code().to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for GetDownloadUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetDownloadUrl")
+            .field("file_code", &self.file_code)
+            .field(
+                "password",
+                &match self.password.is_empty() {
+                    true => "",
+                    false => REDACTED,
+                },
+            )
+            .finish()
+    }
+}
@@ -1,12 +1,15 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use std::fmt;
 
+use crate::UptoboxPath;
+
 /// Input
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFiles {
     /// The folder path
-    path: String,
+    #[serde(serialize_with = "serialize_path")]
+    path: UptoboxPath,
 
     /// Number of files to retrieve
     limit: usize,
@@ -22,7 +25,7 @@ pub struct GetFiles {
 
     /// The search field column name
     #[serde(skip_serializing_if = "Option::is_none")]
-    search_field: Option<OrderBy>,
+    search_field: Option<SearchField>,
 
     /// Search content
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -30,7 +33,7 @@ pub struct GetFiles {
 }
 
 /// Column of the order
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderBy {
     FileName,
@@ -40,9 +43,17 @@ pub enum OrderBy {
     FileDownloads,
 }
 
-/// Direction of the order
-#[derive(Serialize, Debug)]
+/// Column of the search
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    FileName,
+    FileDescr,
+}
+
+/// Direction of the order
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum OrderDir {
     Asc,
     Desc,
@@ -59,13 +70,19 @@ impl fmt::Display for OrderDir {
 
 impl GetFiles {
     /// Create a new instance
-    pub fn new(path: impl Into<String>) -> Self {
+    pub fn new(path: impl Into<UptoboxPath>) -> Self {
         Self {
             path: path.into(),
             ..Default::default()
         }
     }
 
+    /// Set the folder path
+    pub fn path(&mut self, path: impl Into<UptoboxPath>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
     /// Set the limit of files to retrieve
     pub fn limit(&mut self, limit: usize) -> &mut Self {
         self.limit = limit;
@@ -91,17 +108,28 @@ impl GetFiles {
     }
 
     /// Set the search
-    pub fn search(&mut self, search_field: OrderBy, search: impl Into<String>) -> &mut Self {
+    pub fn search(&mut self, search_field: SearchField, search: impl Into<String>) -> &mut Self {
         let _ = self.search_field.insert(search_field);
         let _ = self.search.insert(search.into());
         self
     }
 }
 
+/// Internal
+impl GetFiles {
+    pub(crate) fn path_ref(&self) -> &UptoboxPath {
+        &self.path
+    }
+
+    pub(crate) fn limit_value(&self) -> usize {
+        self.limit
+    }
+}
+
 impl Default for GetFiles {
     fn default() -> Self {
         Self {
-            path: "///".into(),
+            path: UptoboxPath::root(),
             limit: 100,
             offset: 0,
             order_by: OrderBy::FileName,
@@ -111,3 +139,7 @@ impl Default for GetFiles {
         }
     }
 }
+
+fn serialize_path<S: Serializer>(path: &UptoboxPath, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_listing_path())
+}
@@ -1,4 +1,8 @@
+pub mod check_links;
+pub mod find_duplicates;
 pub mod get_download_url;
 pub mod get_files;
 pub mod get_files_from_public_folder;
+pub mod search_all;
 pub mod update_file;
+pub mod walk;
@@ -0,0 +1,57 @@
+use crate::{SearchField, UptoboxPath};
+
+/// Input
+#[derive(Debug, Clone)]
+pub struct SearchAll {
+    /// The folder path where the search starts
+    pub(crate) path: UptoboxPath,
+
+    /// The search field column name
+    pub(crate) search_field: SearchField,
+
+    /// Search content
+    pub(crate) search: String,
+
+    /// Maximum number of files to retrieve
+    pub(crate) limit: Option<usize>,
+
+    /// Maximum depth of the folders searched, relative to the first folder
+    pub(crate) max_depth: Option<usize>,
+}
+
+impl SearchAll {
+    /// Create a new instance, searching the names of the files of the whole account
+    pub fn new(search: impl Into<String>) -> Self {
+        Self {
+            path: UptoboxPath::root(),
+            search_field: SearchField::FileName,
+            search: search.into(),
+            limit: None,
+            max_depth: None,
+        }
+    }
+
+    /// Set the folder path where the search starts
+    pub fn path(&mut self, path: impl Into<UptoboxPath>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Set the search field column name
+    pub fn search_field(&mut self, search_field: SearchField) -> &mut Self {
+        self.search_field = search_field;
+        self
+    }
+
+    /// Set the maximum number of files to retrieve, the search stops once it is reached
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        let _ = self.limit.insert(limit);
+        self
+    }
+
+    /// Set the maximum depth of the folders searched, 0 only searches the first folder
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut Self {
+        let _ = self.max_depth.insert(max_depth);
+        self
+    }
+}
@@ -1,7 +1,11 @@
 use serde::Serialize;
+use std::fmt;
+
+use crate::util::REDACTED;
+use crate::{IntoFileCode, UptoboxResult};
 
 /// Input
-#[derive(Serialize, Debug)]
+#[derive(Serialize)]
 pub struct UpdateFile {
     /// The file code
     file_code: String,
@@ -27,11 +31,11 @@ impl UpdateFile {
     /// Create a new instance
     ///
     /// **1 modifier is requiered for the request to succeed (eg. name, description)**
-    pub fn new(file_code: impl Into<String>) -> Self {
-        Self {
-            file_code: file_code.into(),
+    pub fn new(file_code: impl IntoFileCode) -> UptoboxResult<Self> {
+        Ok(Self {
+            file_code: file_code.into_file_code()?.code().to_string(),
             ..Default::default()
-        }
+        })
     }
 
     /// Update the file name
@@ -70,3 +74,15 @@ impl Default for UpdateFile {
         }
     }
 }
+
+impl fmt::Debug for UpdateFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateFile")
+            .field("file_code", &self.file_code)
+            .field("new_name", &self.new_name)
+            .field("description", &self.description)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .field("public", &self.public)
+            .finish()
+    }
+}
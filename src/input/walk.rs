@@ -0,0 +1,39 @@
+use crate::{GetFiles, UptoboxPath};
+
+/// Input
+#[derive(Debug, Clone, Default)]
+pub struct Walk {
+    /// The options of the listing of each folder
+    pub(crate) get_files: GetFiles,
+
+    /// Maximum depth of the folders visited, relative to the first folder
+    pub(crate) max_depth: Option<usize>,
+
+    /// Only retrieve the first page of each folder, to walk through the folders without their files
+    pub(crate) first_page_only: bool,
+}
+
+impl Walk {
+    /// Create a new instance
+    pub fn new(path: impl Into<UptoboxPath>) -> Self {
+        let mut walk = Self::default();
+        walk.get_files.path(path);
+        walk
+    }
+
+    /// Set the maximum depth of the folders visited, 0 only visits the first folder
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut Self {
+        let _ = self.max_depth.insert(max_depth);
+        self
+    }
+
+    /// Set the options of the listing of each folder (order, search, limit)
+    ///
+    /// The path and the offset are ignored
+    pub fn get_files(&mut self, get_files: &GetFiles) -> &mut Self {
+        let path = self.get_files.path_ref().clone();
+        self.get_files = get_files.clone();
+        self.get_files.path(path);
+        self
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use futures::TryStreamExt;
+use reqwest::header::RANGE;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::{
+    GetDownloadUrl, GetDownloadUrlResponse, GetFilesFiles, Uptobox, UptoboxPath, UptoboxResult,
+    Walk, WalkFile,
+};
+
+/// Options of the keep-alive of the files
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    expiry: Duration,
+    margin: Duration,
+    now: Option<NaiveDateTime>,
+}
+
+/// Result of the refresh of a file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum KeepAliveStatus {
+    /// The first byte of the file was downloaded
+    Refreshed,
+
+    /// The account must wait before downloading, the file was not refreshed
+    Waiting { seconds: usize },
+
+    /// The link or the download failed
+    Failed { error: String },
+}
+
+/// Line of the keep-alive log
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeepAliveEntry {
+    #[serde(with = "crate::model::timestamp")]
+    pub date: NaiveDateTime,
+    pub path: UptoboxPath,
+    pub file_code: String,
+    pub file_name: String,
+
+    /// The last download date before the refresh, as returned by the api
+    pub last_download: String,
+    #[serde(flatten)]
+    pub status: KeepAliveStatus,
+}
+
+/// Keep-alive log, stored as json lines
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeepAliveLog {
+    pub entries: Vec<KeepAliveEntry>,
+}
+
+impl KeepAlive {
+    /// Create a new instance, for files purged after a number of days without download
+    ///
+    /// The files are refreshed 7 days before their expiry by default
+    pub fn new(expiry_days: i64) -> Self {
+        Self {
+            expiry: Duration::days(expiry_days),
+            margin: Duration::days(7),
+            now: None,
+        }
+    }
+
+    /// Set the number of days before the expiry from which the files are refreshed
+    pub fn margin_days(&mut self, margin_days: i64) -> &mut Self {
+        self.margin = Duration::days(margin_days);
+        self
+    }
+
+    /// Set the date the expiry is checked at, the current UTC date by default
+    pub fn now(&mut self, now: NaiveDateTime) -> &mut Self {
+        let _ = self.now.insert(now);
+        self
+    }
+
+    /// Check whether a file must be refreshed, the files never downloaded are checked against their creation date
+    pub fn needs_refresh(&self, file: &GetFilesFiles) -> bool {
+        let limit = self.current_date() - self.expiry + self.margin;
+
+        file.file_last_download_at()
+            .or_else(|| file.file_created_at())
+            .is_some_and(|date| date <= limit)
+    }
+
+    fn current_date(&self) -> NaiveDateTime {
+        self.now.unwrap_or_else(|| Utc::now().naive_utc())
+    }
+}
+
+impl KeepAliveLog {
+    /// Read a log, one json entry per line
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<serde_json::Result<_>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Export the log as json lines
+    pub fn to_json_lines(&self) -> serde_json::Result<String> {
+        let mut lines = String::new();
+        for entry in &self.entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+
+        Ok(lines)
+    }
+
+    /// Append the entries to a log file, created if missing
+    pub fn append_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let lines = self.to_json_lines()?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(lines.as_bytes())
+    }
+
+    /// Number of files refreshed
+    pub fn refreshed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status == KeepAliveStatus::Refreshed)
+            .count()
+    }
+}
+
+/// Keep-alive
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and select the files close to their expiry
+    pub async fn keep_alive_candidates(
+        &self,
+        walk: &Walk,
+        keep_alive: &KeepAlive,
+    ) -> UptoboxResult<Vec<WalkFile>> {
+        self.walk_files(walk)
+            .try_filter(|file| futures::future::ready(keep_alive.needs_refresh(&file.file)))
+            .try_collect()
+            .await
+    }
+
+    /// Refresh the files close to their expiry, one after the other
+    ///
+    /// A failed refresh is recorded in the log and does not stop the others
+    pub async fn keep_alive(
+        &self,
+        walk: &Walk,
+        keep_alive: &KeepAlive,
+    ) -> UptoboxResult<KeepAliveLog> {
+        let mut log = KeepAliveLog::default();
+
+        for file in self.keep_alive_candidates(walk, keep_alive).await? {
+            let status = match self.refresh_file(&file.file).await {
+                Ok(status) => status,
+                Err(e) => KeepAliveStatus::Failed {
+                    error: e.to_string(),
+                },
+            };
+            log.entries.push(KeepAliveEntry {
+                date: keep_alive.current_date(),
+                path: file.path,
+                file_code: file.file.file_code,
+                file_name: file.file.file_name,
+                last_download: file.file.file_last_download,
+                status,
+            });
+        }
+
+        Ok(log)
+    }
+
+    /// Resolve the download link of a file and download its first byte
+    pub async fn refresh_file(&self, file: &GetFilesFiles) -> UptoboxResult<KeepAliveStatus> {
+        let mut get_download_url = GetDownloadUrl::new(&file.file_code)?;
+        if let Some(password) = &file.file_password {
+            get_download_url.password(password);
+        }
+
+        let link = match self.get_download_url(get_download_url).await? {
+            GetDownloadUrlResponse::Link(link) => link,
+            GetDownloadUrlResponse::Wait(wait) => {
+                return Ok(KeepAliveStatus::Waiting {
+                    seconds: wait.waiting,
+                })
+            }
+        };
+
+        self.send(self.client.get(&link.dl_link).header(RANGE, "bytes=0-0"))
+            .await?;
+
+        Ok(KeepAliveStatus::Refreshed)
+    }
+}
@@ -1,27 +1,74 @@
 #![allow(dead_code)]
 
+use std::fmt;
+use std::time::Instant;
+
 use json_patch::merge as json_merge;
 
-use reqwest::{Client, Method, Response};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, RequestBuilder, Response};
 use serde_json::{json, Value};
 
+mod analytics;
+mod disk_usage;
+mod duplicates;
+mod extract;
+mod file_code;
+mod filter;
 mod input;
+#[cfg(feature = "chrono")]
+mod keep_alive;
+mod link_checker;
+mod manifest;
 mod model;
+mod path;
+mod restore;
+#[cfg(feature = "chrono")]
+mod retention;
+mod search;
 mod util;
-
-use util::deserialize;
-
+mod walk;
+
+use file_code::{dedup_file_codes, join_file_codes};
+use util::{deserialize, http_error, HttpResponse, REDACTED};
+
+pub use analytics::{FileStats, FileTrend, FolderStats, StatsSnapshot, TrendReport};
+pub use disk_usage::{DiskUsage, DiskUsageFile, DiskUsageReport};
+pub use duplicates::{DuplicateGroup, DuplicateReport, KeepStrategy, ResolutionPlan};
+pub use extract::{extract_links, extract_links_from_file, ExtractedLinks, PublicFolder};
+pub use file_code::{FileCode, IntoFileCode};
+pub use filter::FileFilter;
+pub use input::check_links::CheckLinks;
+pub use input::find_duplicates::FindDuplicates;
 pub use input::get_download_url::GetDownloadUrl;
-pub use input::get_files::{GetFiles, OrderBy, OrderDir};
+pub use input::get_files::{GetFiles, OrderBy, OrderDir, SearchField};
 pub use input::get_files_from_public_folder::GetFilesFromPublicFolder;
+pub use input::search_all::SearchAll;
 pub use input::update_file::UpdateFile;
+pub use input::walk::Walk;
+#[cfg(feature = "chrono")]
+pub use keep_alive::{KeepAlive, KeepAliveEntry, KeepAliveLog, KeepAliveStatus};
+pub use link_checker::{LinkReport, LinkReportEntry, LinkStatus};
+pub use manifest::{
+    FileChange, Manifest, ManifestDiff, ManifestFile, ManifestFolder, MANIFEST_VERSION,
+};
+pub use model::api_response::ApiResponse;
 pub use model::get_account::GetAccountResponse;
-pub use model::get_account_payments::GetAccountPaymentsResponse;
+pub use model::get_account_payments::{GetAccountPaymentsResponse, PaymentStatus, PaymentType};
 pub use model::get_download_url::{GetDownloadUrlResponse, GetDownloadUrlLink, GetDownloadUrlWait};
+pub use model::get_files::{FileVisibility, TranscodingState};
 pub use model::get_files::{GetFilesResponse, GetFilesCurrentFolder, GetFilesFolders, GetFilesFiles};
 pub use model::get_files_from_public_folder::GetFilesFromPublicFolderResponse;
+pub use model::get_files_informations::{FileInfo, FileInfoError, FileInfoErrorCode};
 pub use model::get_files_informations::{GetFilesInformationsResponse, GetFilesInformationsError};
 pub use model::get_upload_url::GetUploadUrlResponse;
+pub use model::raw::RawResponse;
+pub use path::UptoboxPath;
+pub use reqwest::Method;
+pub use restore::{MetadataChange, RestoreAction, RestorePlan};
+#[cfg(feature = "chrono")]
+pub use retention::{RetentionMatch, RetentionPolicy, RetentionReport, RetentionRule};
+pub use walk::{WalkFile, WalkFolder};
 
 use model::generic::GenericEmpyDataResponseWrapper;
 use model::generic::{GenericMessageResponseWrapper, GenericUpdatedResponseWrapper};
@@ -30,7 +77,7 @@ use model::get_account_payments::GetAccountPaymentsResponseWrapper;
 use model::get_download_url::GetDownloadUrlResponseWrapper;
 use model::get_files::GetFilesResponseWrapper;
 use model::get_files_from_public_folder::GetFilesFromPublicFolderResponseWrapper;
-use model::get_files_informations::GetFilesInformationsResponseWrapper;
+use model::get_files_informations::{match_informations, GetFilesInformationsResponseWrapper};
 use model::get_upload_url::GetUploadUrlResponseWrapper;
 
 const BASE_URL: &str = "https://uptobox.com/api/";
@@ -39,40 +86,78 @@ const BASE_URL: &str = "https://uptobox.com/api/";
 pub struct Uptobox {
     client: Client,
     key: &'static str,
+    lenient: bool,
+}
+
+impl fmt::Debug for Uptobox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Uptobox")
+            .field("client", &self.client)
+            .field("key", &REDACTED)
+            .field("lenient", &self.lenient)
+            .finish()
+    }
 }
 
 /// My account
 impl Uptobox {
     /// Retrieve user data
     pub async fn get_account(&self) -> UptoboxResult<GetAccountResponse> {
+        self.get_account_with_meta().await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_account`], with the metadata of the response
+    pub async fn get_account_with_meta(&self) -> UptoboxResult<ApiResponse<GetAccountResponse>> {
         let response = self.get("user/me", json!({})).await?;
 
-        deserialize::<GetAccountResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetAccountResponseWrapper>(&response)
     }
 
     /// Update Direct Download
     pub async fn update_account_dd(&self, ssl: bool) -> UptoboxResult {
+        self.update_account_dd_with_meta(ssl).await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::update_account_dd`], with the metadata of the response
+    pub async fn update_account_dd_with_meta(&self, ssl: bool) -> UptoboxResult<ApiResponse<()>> {
         let response = self
             .patch("user/settings", json!({ "directDownload": ssl as usize }))
             .await?;
 
-        deserialize::<GenericEmpyDataResponseWrapper>(&response).map(|_| ())
+        deserialize::<GenericEmpyDataResponseWrapper>(&response)
     }
 
     /// Update Direct Download
     pub async fn update_account_security_lock(&self, ssl: bool) -> UptoboxResult {
+        self.update_account_security_lock_with_meta(ssl)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::update_account_security_lock`], with the metadata of the response
+    pub async fn update_account_security_lock_with_meta(
+        &self,
+        ssl: bool,
+    ) -> UptoboxResult<ApiResponse<()>> {
         let response = self
             .patch("user/securityLock", json!({ "securityLock": ssl as usize }))
             .await?;
 
-        deserialize::<GenericEmpyDataResponseWrapper>(&response).map(|_| ())
+        deserialize::<GenericEmpyDataResponseWrapper>(&response)
     }
 
     /// Retrieve user payments
     pub async fn get_account_payments(&self) -> UptoboxResult<Vec<GetAccountPaymentsResponse>> {
+        self.get_account_payments_with_meta().await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_account_payments`], with the metadata of the response
+    pub async fn get_account_payments_with_meta(
+        &self,
+    ) -> UptoboxResult<ApiResponse<Vec<GetAccountPaymentsResponse>>> {
         let response = self.get("user/payments/get", json!({})).await?;
 
-        deserialize::<GetAccountPaymentsResponseWrapper>(&response).map(|r| r.data.list)
+        deserialize::<GetAccountPaymentsResponseWrapper>(&response)
     }
 }
 
@@ -85,6 +170,16 @@ impl Uptobox {
         &self,
         get_waiting_token: GetDownloadUrl,
     ) -> UptoboxResult<GetDownloadUrlResponse> {
+        self.get_download_url_with_meta(get_waiting_token)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_download_url`], with the metadata of the response
+    pub async fn get_download_url_with_meta(
+        &self,
+        get_waiting_token: GetDownloadUrl,
+    ) -> UptoboxResult<ApiResponse<GetDownloadUrlResponse>> {
         let response = self
             .get(
                 "link",
@@ -92,23 +187,34 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GetDownloadUrlResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetDownloadUrlResponseWrapper>(&response)
     }
 
     /// Get the download link with a waiting_token
     pub async fn get_download_url_waiting_token(
         &self,
-        file_code: impl Into<String>,
+        file_code: impl IntoFileCode,
         waiting_token: impl Into<String>,
     ) -> UptoboxResult<GetDownloadUrlResponse> {
+        self.get_download_url_waiting_token_with_meta(file_code, waiting_token)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_download_url_waiting_token`], with the metadata of the response
+    pub async fn get_download_url_waiting_token_with_meta(
+        &self,
+        file_code: impl IntoFileCode,
+        waiting_token: impl Into<String>,
+    ) -> UptoboxResult<ApiResponse<GetDownloadUrlResponse>> {
         let response = self
             .get(
                 "link",
-                json!({ "file_code": file_code.into(), "waitingToken": waiting_token.into() }),
+                json!({ "file_code": file_code.into_file_code()?.code(), "waitingToken": waiting_token.into() }),
             )
             .await?;
 
-        deserialize::<GetDownloadUrlResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetDownloadUrlResponseWrapper>(&response)
     }
 
     /// Get a waiting token without an account
@@ -116,6 +222,16 @@ impl Uptobox {
         &self,
         get_waiting_token: GetDownloadUrl,
     ) -> UptoboxResult<GetDownloadUrlResponse> {
+        self.public_get_download_url_with_meta(get_waiting_token)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::public_get_download_url`], with the metadata of the response
+    pub async fn public_get_download_url_with_meta(
+        &self,
+        get_waiting_token: GetDownloadUrl,
+    ) -> UptoboxResult<ApiResponse<GetDownloadUrlResponse>> {
         let response = self
             .public_get(
                 "link",
@@ -123,23 +239,34 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GetDownloadUrlResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetDownloadUrlResponseWrapper>(&response)
     }
 
     /// Get the download link with a waiting_token without an account
     pub async fn public_get_download_url_waiting_token(
         &self,
-        file_code: impl Into<String>,
+        file_code: impl IntoFileCode,
         waiting_token: impl Into<String>,
     ) -> UptoboxResult<GetDownloadUrlResponse> {
+        self.public_get_download_url_waiting_token_with_meta(file_code, waiting_token)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::public_get_download_url_waiting_token`], with the metadata of the response
+    pub async fn public_get_download_url_waiting_token_with_meta(
+        &self,
+        file_code: impl IntoFileCode,
+        waiting_token: impl Into<String>,
+    ) -> UptoboxResult<ApiResponse<GetDownloadUrlResponse>> {
         let response = self
             .public_get(
                 "link",
-                json!({ "file_code": file_code.into(), "waitingToken": waiting_token.into() }),
+                json!({ "file_code": file_code.into_file_code()?.code(), "waitingToken": waiting_token.into() }),
             )
             .await?;
 
-        deserialize::<GetDownloadUrlResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetDownloadUrlResponseWrapper>(&response)
     }
 }
 
@@ -148,15 +275,49 @@ impl Uptobox {
     /// Retrieve file informations
     ///
     /// For each file code provided, you can add a password separated by ':' For example : filecode1:password1,filecode2:password2
+    /// or use [`FileCode::with_password`]
     pub async fn get_files_informations(
         &self,
-        file_codes: Vec<&str>,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
     ) -> UptoboxResult<Vec<GetFilesInformationsResponse>> {
+        self.get_files_informations_with_meta(file_codes)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_files_informations`], with the metadata of the response
+    pub async fn get_files_informations_with_meta(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+    ) -> UptoboxResult<ApiResponse<Vec<GetFilesInformationsResponse>>> {
         let response = self
-            .public_get("link/info", json!({ "fileCodes": file_codes.join(",") }))
+            .public_get(
+                "link/info",
+                json!({ "fileCodes": join_file_codes(file_codes, true)? }),
+            )
+            .await?;
+
+        deserialize::<GetFilesInformationsResponseWrapper>(&response)
+    }
+
+    /// Retrieve file informations, with a result for each file code in the order of the input
+    ///
+    /// Unlike [`Uptobox::get_files_informations`], the unavailable files are returned as errors.
+    /// Each code is requested once, and its result is repeated for every occurrence in the input
+    pub async fn get_files_informations_results(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+    ) -> UptoboxResult<Vec<(FileCode, Result<FileInfo, FileInfoError>)>> {
+        let file_codes = file_codes
+            .into_iter()
+            .map(IntoFileCode::into_file_code)
+            .collect::<UptoboxResult<Vec<FileCode>>>()?;
+
+        let informations = self
+            .get_files_informations(dedup_file_codes(file_codes.clone()))
             .await?;
 
-        deserialize::<GetFilesInformationsResponseWrapper>(&response).map(|r| r.data.list)
+        Ok(match_informations(file_codes, informations))
     }
 
     /// Retrieve files in public folder
@@ -164,6 +325,16 @@ impl Uptobox {
         &self,
         get_files_from_public_folder: &GetFilesFromPublicFolder,
     ) -> UptoboxResult<Vec<GetFilesFromPublicFolderResponse>> {
+        self.get_files_from_public_folder_with_meta(get_files_from_public_folder)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_files_from_public_folder`], with the metadata of the response
+    pub async fn get_files_from_public_folder_with_meta(
+        &self,
+        get_files_from_public_folder: &GetFilesFromPublicFolder,
+    ) -> UptoboxResult<ApiResponse<Vec<GetFilesFromPublicFolderResponse>>> {
         let response = self
             .public_get(
                 "user/public",
@@ -171,7 +342,7 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GetFilesFromPublicFolderResponseWrapper>(&response).map(|r| r.data.list)
+        deserialize::<GetFilesFromPublicFolderResponseWrapper>(&response)
     }
 }
 
@@ -181,6 +352,14 @@ impl Uptobox {
     ///
     /// Retrieve files and folders
     pub async fn get_files(&self, get_files: &GetFiles) -> UptoboxResult<GetFilesResponse> {
+        self.get_files_with_meta(get_files).await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_files`], with the metadata of the response
+    pub async fn get_files_with_meta(
+        &self,
+        get_files: &GetFiles,
+    ) -> UptoboxResult<ApiResponse<GetFilesResponse>> {
         let response = self
             .get(
                 "user/files",
@@ -188,13 +367,23 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GetFilesResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetFilesResponseWrapper>(&response)
     }
 
     /// Update file informations
     ///
     /// The following informations can be updated. Filename, Description, Password, Public
     pub async fn update_file(&self, update_file: &UpdateFile) -> UptoboxResult<usize> {
+        self.update_file_with_meta(update_file)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::update_file`], with the metadata of the response
+    pub async fn update_file_with_meta(
+        &self,
+        update_file: &UpdateFile,
+    ) -> UptoboxResult<ApiResponse<usize>> {
         let response = self
             .patch(
                 "user/files",
@@ -202,19 +391,34 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GenericUpdatedResponseWrapper>(&response).map(|r| r.data.updated)
+        deserialize::<GenericUpdatedResponseWrapper>(&response)
     }
 
     /// Not working
-    pub async fn update_public(&self, file_codes: Vec<&str>, public: bool) -> UptoboxResult<usize> {
+    pub async fn update_public(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+        public: bool,
+    ) -> UptoboxResult<usize> {
+        self.update_public_with_meta(file_codes, public)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::update_public`], with the metadata of the response
+    pub async fn update_public_with_meta(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+        public: bool,
+    ) -> UptoboxResult<ApiResponse<usize>> {
         let response = self
             .patch(
                 "user/files",
-                json!({ "file_codes": file_codes.join(","), "public": public }),
+                json!({ "file_codes": join_file_codes(file_codes, false)?, "public": public }),
             )
             .await?;
 
-        deserialize::<GenericUpdatedResponseWrapper>(&response).map(|r| r.data.updated)
+        deserialize::<GenericUpdatedResponseWrapper>(&response)
     }
 
     /// Move a folder to another location
@@ -223,6 +427,17 @@ impl Uptobox {
         fld_id: usize,
         destination_fld_id: usize,
     ) -> UptoboxResult<String> {
+        self.move_folder_with_meta(fld_id, destination_fld_id)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::move_folder`], with the metadata of the response
+    pub async fn move_folder_with_meta(
+        &self,
+        fld_id: usize,
+        destination_fld_id: usize,
+    ) -> UptoboxResult<ApiResponse<String>> {
         let response = self
             .patch(
                 "user/files",
@@ -230,39 +445,61 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GenericMessageResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GenericMessageResponseWrapper>(&response)
     }
 
     /// Move one or multiple files to another location
     pub async fn move_files(
         &self,
-        file_codes: Vec<&str>,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
         destination_fld_id: usize,
     ) -> UptoboxResult<usize> {
+        self.move_files_with_meta(file_codes, destination_fld_id)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::move_files`], with the metadata of the response
+    pub async fn move_files_with_meta(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+        destination_fld_id: usize,
+    ) -> UptoboxResult<ApiResponse<usize>> {
         let response = self
             .patch(
                 "user/files",
-                json!({ "file_codes": file_codes.join(","), "destination_fld_id": destination_fld_id, "action": "move" }),
+                json!({ "file_codes": join_file_codes(file_codes, false)?, "destination_fld_id": destination_fld_id, "action": "move" }),
             )
             .await?;
 
-        deserialize::<GenericUpdatedResponseWrapper>(&response).map(|r| r.data.updated)
+        deserialize::<GenericUpdatedResponseWrapper>(&response)
     }
 
     /// Copy one or multiple files to another location
     pub async fn copy_files(
         &self,
-        file_codes: Vec<&str>,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
         destination_fld_id: usize,
     ) -> UptoboxResult<usize> {
+        self.copy_files_with_meta(file_codes, destination_fld_id)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::copy_files`], with the metadata of the response
+    pub async fn copy_files_with_meta(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+        destination_fld_id: usize,
+    ) -> UptoboxResult<ApiResponse<usize>> {
         let response = self
             .patch(
                 "user/files",
-                json!({ "file_codes": file_codes.join(","), "destination_fld_id": destination_fld_id, "action": "copy" }),
+                json!({ "file_codes": join_file_codes(file_codes, false)?, "destination_fld_id": destination_fld_id, "action": "copy" }),
             )
             .await?;
 
-        deserialize::<GenericUpdatedResponseWrapper>(&response).map(|r| r.data.updated)
+        deserialize::<GenericUpdatedResponseWrapper>(&response)
     }
 
     /// Rename a folder
@@ -271,6 +508,17 @@ impl Uptobox {
         fld_id: usize,
         new_name: impl Into<String>,
     ) -> UptoboxResult<String> {
+        self.rename_folder_with_meta(fld_id, new_name)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::rename_folder`], with the metadata of the response
+    pub async fn rename_folder_with_meta(
+        &self,
+        fld_id: usize,
+        new_name: impl Into<String>,
+    ) -> UptoboxResult<ApiResponse<String>> {
         let response = self
             .patch(
                 "user/files",
@@ -278,41 +526,107 @@ impl Uptobox {
             )
             .await?;
 
-        deserialize::<GenericMessageResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GenericMessageResponseWrapper>(&response)
     }
 
     /// Create a folder
     pub async fn create_folder(
         &self,
-        path: impl Into<String>,
+        path: impl Into<UptoboxPath>,
         name: impl Into<String>,
     ) -> UptoboxResult<String> {
+        self.create_folder_with_meta(path, name)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::create_folder`], with the metadata of the response
+    pub async fn create_folder_with_meta(
+        &self,
+        path: impl Into<UptoboxPath>,
+        name: impl Into<String>,
+    ) -> UptoboxResult<ApiResponse<String>> {
         let response = self
             .put(
                 "user/files",
-                json!({ "path": path.into(), "name": name.into() }),
+                json!({ "path": path.into().to_folder_path(), "name": name.into() }),
             )
             .await?;
 
-        deserialize::<GenericMessageResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GenericMessageResponseWrapper>(&response)
     }
 
     /// Delete one or multiple files
-    pub async fn delete_files(&self, file_codes: Vec<&str>) -> UptoboxResult<usize> {
+    pub async fn delete_files(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+    ) -> UptoboxResult<usize> {
+        self.delete_files_with_meta(file_codes)
+            .await
+            .map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::delete_files`], with the metadata of the response
+    pub async fn delete_files_with_meta(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+    ) -> UptoboxResult<ApiResponse<usize>> {
         let response = self
-            .delete("user/files", json!({ "file_codes": file_codes.join(",") }))
+            .delete(
+                "user/files",
+                json!({ "file_codes": join_file_codes(file_codes, false)? }),
+            )
             .await?;
 
-        deserialize::<GenericUpdatedResponseWrapper>(&response).map(|r| r.data.updated)
+        deserialize::<GenericUpdatedResponseWrapper>(&response)
     }
 
     /// Delete a folder
     pub async fn delete_folder(&self, fld_id: usize) -> UptoboxResult<String> {
+        self.delete_folder_with_meta(fld_id).await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::delete_folder`], with the metadata of the response
+    pub async fn delete_folder_with_meta(
+        &self,
+        fld_id: usize,
+    ) -> UptoboxResult<ApiResponse<String>> {
         let response = self
             .delete("user/files", json!({ "fld_id": fld_id }))
             .await?;
 
-        deserialize::<GenericMessageResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GenericMessageResponseWrapper>(&response)
+    }
+}
+
+/// Path helpers
+impl Uptobox {
+    /// Retrieve the id of a folder from its path
+    pub async fn folder_id(&self, path: impl Into<UptoboxPath>) -> UptoboxResult<usize> {
+        let response = self.get_files(GetFiles::new(path).limit(1)).await?;
+
+        Ok(response.current_folder.fld_id)
+    }
+
+    /// Create a folder and its missing parents, and return its id
+    pub async fn create_folder_all(&self, path: impl Into<UptoboxPath>) -> UptoboxResult<usize> {
+        let path = path.into();
+        let mut current = UptoboxPath::root();
+
+        for name in path.segments() {
+            let response = self.get_files(GetFiles::new(&current).limit(1)).await?;
+            let exists = response
+                .folders
+                .iter()
+                .any(|folder| folder.path(&current).file_name() == Some(name));
+            if !exists {
+                self.create_folder(&current, name).await?;
+            }
+
+            current = current.join(name);
+        }
+
+        self.folder_id(&path).await
     }
 }
 
@@ -320,9 +634,56 @@ impl Uptobox {
 impl Uptobox {
     /// Retrieve an upload url
     pub async fn get_upload_url(&self) -> UptoboxResult<GetUploadUrlResponse> {
+        self.get_upload_url_with_meta().await.map(|r| r.data)
+    }
+
+    /// Same as [`Uptobox::get_upload_url`], with the metadata of the response
+    pub async fn get_upload_url_with_meta(
+        &self,
+    ) -> UptoboxResult<ApiResponse<GetUploadUrlResponse>> {
         let response = self.get("upload", json!({})).await?;
 
-        deserialize::<GetUploadUrlResponseWrapper>(&response).map(|r| r.data)
+        deserialize::<GetUploadUrlResponseWrapper>(&response)
+    }
+}
+
+/// Raw
+impl Uptobox {
+    /// Call any route of the api and retrieve the raw data
+    ///
+    /// The params are sent as a query string for `GET` requests and as a json body otherwise.
+    /// If `authenticated` is true, the token is added to the params
+    pub async fn call_raw(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        params: Value,
+        authenticated: bool,
+    ) -> UptoboxResult<RawResponse> {
+        self.call_raw_with_meta(method, path, params, authenticated)
+            .await
+            .map(RawResponse::from)
+    }
+
+    /// Same as [`Uptobox::call_raw`], with the metadata of the response
+    pub async fn call_raw_with_meta(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        params: Value,
+        authenticated: bool,
+    ) -> UptoboxResult<ApiResponse<Value>> {
+        let params = match authenticated {
+            true => self.add_token_auth(params),
+            false => params,
+        };
+
+        let response = match method {
+            Method::GET => self.public_get(path, params).await?,
+            _ => self.public_req(method, path, params).await?,
+        };
+
+        deserialize::<RawResponse>(&response)
     }
 }
 
@@ -332,21 +693,31 @@ impl Uptobox {
         Self {
             client: Client::new(),
             key,
+            lenient: false,
         }
     }
 
+    /// Enable the lenient mode
+    ///
+    /// The optional fields of an unexpected type are deserialized as `None` instead of failing the call,
+    /// and a warning is recorded in [`ApiResponse::warnings`]
+    pub fn lenient(&mut self, lenient: bool) -> &mut Self {
+        self.lenient = lenient;
+        self
+    }
+
     /// Make a Delete request
-    async fn delete(&self, path: impl Into<String>, body: Value) -> UptoboxResult<String> {
+    async fn delete(&self, path: impl Into<String>, body: Value) -> UptoboxResult<HttpResponse> {
         self.req(Method::DELETE, path, body).await
     }
 
     /// Make a Put request
-    async fn put(&self, path: impl Into<String>, body: Value) -> UptoboxResult<String> {
+    async fn put(&self, path: impl Into<String>, body: Value) -> UptoboxResult<HttpResponse> {
         self.req(Method::PUT, path, body).await
     }
 
     /// Make a Patch request
-    async fn patch(&self, path: impl Into<String>, body: Value) -> UptoboxResult<String> {
+    async fn patch(&self, path: impl Into<String>, body: Value) -> UptoboxResult<HttpResponse> {
         self.req(Method::PATCH, path, body).await
     }
 
@@ -356,35 +727,69 @@ impl Uptobox {
         method: Method,
         path: impl Into<String>,
         body: Value,
-    ) -> UptoboxResult<String> {
+    ) -> UptoboxResult<HttpResponse> {
         let body = self.add_token_auth(body);
+        self.public_req(method, path, body).await
+    }
 
-        let res = self
+    /// Make a request without adding the auth token
+    async fn public_req(
+        &self,
+        method: Method,
+        path: impl Into<String>,
+        body: Value,
+    ) -> UptoboxResult<HttpResponse> {
+        let path = path.into();
+        let request = self
             .client
-            .request(method, format!("{BASE_URL}{}", path.into()))
-            .json(&body)
-            .send()
-            .await
-            .map_err(Error::HttpRequest)?;
+            .request(method, format!("{BASE_URL}{path}"))
+            .json(&body);
 
-        self.parse_body(res).await
+        self.send(request).await
     }
 
     /// Get a public route
-    async fn public_get(&self, path: impl Into<String>, params: Value) -> UptoboxResult<String> {
-        let res = self
-            .client
-            .get(format!("{BASE_URL}{}", path.into()))
-            .query(&params)
-            .send()
-            .await
-            .map_err(Error::HttpRequest)?;
+    async fn public_get(
+        &self,
+        path: impl Into<String>,
+        params: Value,
+    ) -> UptoboxResult<HttpResponse> {
+        let path = path.into();
+        let request = self.client.get(format!("{BASE_URL}{path}")).query(&params);
 
-        self.parse_body(res).await
+        self.send(request).await
+    }
+
+    /// Send a request and read its body
+    ///
+    /// With the `tracing` feature, the request is wrapped in a span, see [`request_span`]
+    async fn send(&self, request: RequestBuilder) -> UptoboxResult<HttpResponse> {
+        let request = request.build().map_err(Error::http_request)?;
+        #[cfg(feature = "tracing")]
+        let span = request_span(&request);
+
+        let start = Instant::now();
+        let response = async {
+            let res = self
+                .client
+                .execute(request)
+                .await
+                .map_err(Error::http_request)?;
+            self.parse_body(res, start).await
+        };
+
+        #[cfg(feature = "tracing")]
+        let response = async {
+            let mut response = tracing::Instrument::instrument(response, span.clone()).await;
+            record_response(span, start, &mut response);
+            response
+        };
+
+        response.await
     }
 
     /// Get a private route
-    async fn get(&self, path: impl Into<String>, params: Value) -> UptoboxResult<String> {
+    async fn get(&self, path: impl Into<String>, params: Value) -> UptoboxResult<HttpResponse> {
         let params = self.add_token_auth(params);
         self.public_get(path, params).await
     }
@@ -396,14 +801,76 @@ impl Uptobox {
         secret
     }
 
-    /// Parse the body of a response as json, and check if the response status is 200.
-    /// If the response status is 200, it returns the json value, otherwise it returns an Error::HttpResponseCode variant with the status code.
-    async fn parse_body(&self, res: Response) -> UptoboxResult<String> {
-        if res.status() == 200 {
-            Ok(res.text().await.map_err(Error::HttpRequest)?)
+    /// Read the body of a response, and check if the response status is a success.
+    /// If the response status is a success, it returns the body with its metadata.
+    /// Otherwise it returns the error of the api if the body holds one, or an Error::HttpResponseCode variant with the raw response.
+    async fn parse_body(&self, res: Response, start: Instant) -> UptoboxResult<HttpResponse> {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("status_code", res.status().as_u16());
+
+        let status = res.status();
+        let headers = res.headers().clone();
+        let path = res.url().path().to_string();
+        let body = res.text().await.map_err(Error::http_request)?;
+
+        if status.is_success() {
+            Ok(HttpResponse {
+                body,
+                headers,
+                elapsed: start.elapsed(),
+                lenient: self.lenient,
+                #[cfg(feature = "tracing")]
+                span: tracing::Span::none(),
+            })
         } else {
-            Err(Error::HttpResponseCode(res.status().as_u16()))
+            Err(http_error(HttpErrorResponse {
+                status: status.as_u16(),
+                path,
+                headers,
+                body,
+            }))
+        }
+    }
+}
+
+/// Span of a request
+///
+/// Only the route of the api is recorded, the params and the body (which hold the token and the passwords) are never recorded.
+/// The other urls (eg. the downloads) hold a token in their path, only their host is recorded
+#[cfg(feature = "tracing")]
+fn request_span(request: &reqwest::Request) -> tracing::Span {
+    let url = request.url();
+    let endpoint = match url.as_str().starts_with(BASE_URL) {
+        true => url.path().trim_start_matches("/api/"),
+        false => url.host_str().unwrap_or_default(),
+    };
+
+    tracing::debug_span!(
+        "uptobox_request",
+        endpoint,
+        method = %request.method(),
+        status_code = tracing::field::Empty,
+        api_status_code = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+        bytes = tracing::field::Empty,
+    )
+}
+
+/// Record the outcome of a request in its span, and attach the span to the response
+#[cfg(feature = "tracing")]
+fn record_response(
+    span: tracing::Span,
+    start: Instant,
+    response: &mut UptoboxResult<HttpResponse>,
+) {
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    match response {
+        Ok(response) => {
+            span.record("bytes", response.body.len());
+            tracing::debug!(parent: &span, "request succeeded");
+            response.span = span;
         }
+        Err(e) => tracing::warn!(parent: &span, error = %e, "request failed"),
     }
 }
 
@@ -421,8 +888,154 @@ pub enum Error {
     #[error("Bad response")]
     HttpRequest(#[source] reqwest::Error),
 
-    #[error("Bad response with status code: {0}")]
-    HttpResponseCode(u16),
+    #[error("Invalid file code: {0}")]
+    InvalidFileCode(String),
+
+    #[error("Bad response with status code: {}", .0.status)]
+    HttpResponseCode(Box<HttpErrorResponse>),
+
+    #[error("Bad response with status code: {}, {}", .0.status, .1)]
+    HttpResponseApiError(Box<HttpErrorResponse>, Box<Error>),
+}
+
+/// Raw response of a request that failed with a status code other than 200
+#[derive(Debug)]
+pub struct HttpErrorResponse {
+    /// The http status code
+    pub status: u16,
+
+    /// The path of the url, without the query string
+    pub path: String,
+
+    /// The http headers of the response
+    pub headers: HeaderMap,
+
+    /// The body of the response
+    pub body: String,
+}
+
+impl Error {
+    /// Wrap a reqwest error, the url is removed because its query string holds the token
+    fn http_request(e: reqwest::Error) -> Self {
+        Error::HttpRequest(e.without_url())
+    }
+}
+
+/// Status codes of the api
+const API_INVALID_CREDENTIALS: usize = 2;
+const API_PREMIUM_REQUIRED: usize = 6;
+const API_INVALID_TOKEN: usize = 13;
+const API_WAITING_NEEDED: usize = 16;
+const API_FILE_NOT_FOUND: usize = 28;
+const API_NEED_TO_WAIT: usize = 39;
+
+/// Classification
+impl Error {
+    /// The http status code, if the request reached the server
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            Error::HttpRequest(e) => e.status().map(|s| s.as_u16()),
+            Error::HttpResponseCode(r) | Error::HttpResponseApiError(r, _) => Some(r.status),
+            _ => None,
+        }
+    }
+
+    /// The status code returned by the api
+    pub fn api_status(&self) -> Option<usize> {
+        self.api_error().map(|(status_code, _)| status_code)
+    }
+
+    /// The token or the credentials are invalid
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(
+            self.api_status(),
+            Some(API_INVALID_CREDENTIALS | API_INVALID_TOKEN)
+        ) || matches!(self.http_status(), Some(401 | 403))
+    }
+
+    /// The file or the route does not exist
+    pub fn is_not_found(&self) -> bool {
+        self.api_status() == Some(API_FILE_NOT_FOUND) || self.http_status() == Some(404)
+    }
+
+    /// A premium account is required
+    pub fn is_premium_required(&self) -> bool {
+        self.api_status() == Some(API_PREMIUM_REQUIRED)
+    }
+
+    /// Too many requests, or a waiting time is required before the next one
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(
+            self.api_status(),
+            Some(API_WAITING_NEEDED | API_NEED_TO_WAIT)
+        ) || self.http_status() == Some(429)
+    }
+
+    /// The same request may succeed later
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::HttpRequest(e) if e.is_timeout() || e.is_connect() => true,
+            _ => {
+                self.is_rate_limited() || matches!(self.http_status(), Some(500 | 502 | 503 | 504))
+            }
+        }
+    }
+
+    /// Number of seconds to wait before the next request
+    ///
+    /// It is read from the `waiting` field of the api error, or from the `Retry-After` header
+    pub fn wait_seconds(&self) -> Option<u64> {
+        let waiting = self.api_error().and_then(|(_, data)| {
+            match serde_json::from_str::<Value>(data).ok()? {
+                Value::Object(data) => data.get("waiting")?.as_u64(),
+                Value::Number(waiting) => waiting.as_u64(),
+                _ => None,
+            }
+        });
+
+        waiting.or_else(|| match self {
+            Error::HttpResponseCode(r) | Error::HttpResponseApiError(r, _) => r
+                .headers
+                .get(RETRY_AFTER)?
+                .to_str()
+                .ok()?
+                .trim()
+                .parse()
+                .ok(),
+            _ => None,
+        })
+    }
+
+    /// The status code and the data of the api error
+    fn api_error(&self) -> Option<(usize, &str)> {
+        match self {
+            Error::ParseResponse(status_code, _, data) => Some((*status_code, data)),
+            Error::HttpResponseApiError(_, error) => error.api_error(),
+            _ => None,
+        }
+    }
 }
 
 pub type UptoboxResult<T = ()> = Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_request_without_url() {
+        let url = reqwest::Url::parse("https://uptobox.com/api/user/me?token=s3cr3t").unwrap();
+        let error = Client::new()
+            .get(url.clone())
+            .header("x-invalid", "\n")
+            .build()
+            .unwrap_err()
+            .with_url(url);
+        assert!(error.to_string().contains("s3cr3t"));
+
+        let error = Error::http_request(error);
+        assert!(!error.to_string().contains("s3cr3t"));
+        assert!(!format!("{error:?}").contains("s3cr3t"));
+        assert!(!format!("{error:?}").contains("token="));
+    }
+}
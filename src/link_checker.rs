@@ -0,0 +1,293 @@
+use futures::StreamExt;
+use serde::Serialize;
+
+use crate::file_code::dedup_file_codes;
+use crate::util::csv_field;
+use crate::{
+    CheckLinks, Error, FileCode, FileInfo, FileInfoError, FileInfoErrorCode, IntoFileCode, Uptobox,
+    UptoboxResult,
+};
+
+/// Availability of a link
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStatus {
+    /// The file can be downloaded
+    Alive,
+
+    /// The file can only be downloaded with a premium account
+    PremiumOnly,
+
+    /// The file does not exist anymore
+    Dead,
+
+    /// The request checking the file failed or the api returned nothing for it, its availability is unknown
+    Unchecked,
+}
+
+/// Availability of a file
+#[derive(Serialize, Debug, Clone)]
+pub struct LinkReportEntry {
+    pub file_code: String,
+    pub status: LinkStatus,
+    pub file_name: Option<String>,
+    pub file_size: usize,
+    pub available_uts: bool,
+    pub error_code: Option<usize>,
+    pub error_message: Option<String>,
+}
+
+/// Availability of a list of files
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct LinkReport {
+    /// The files, in the order of the input, without duplicates
+    pub entries: Vec<LinkReportEntry>,
+}
+
+impl LinkReport {
+    /// Files that can be downloaded
+    pub fn alive(&self) -> impl Iterator<Item = &LinkReportEntry> {
+        self.with_status(LinkStatus::Alive)
+    }
+
+    /// Files that can only be downloaded with a premium account
+    pub fn premium_only(&self) -> impl Iterator<Item = &LinkReportEntry> {
+        self.with_status(LinkStatus::PremiumOnly)
+    }
+
+    /// Files that do not exist anymore
+    pub fn dead(&self) -> impl Iterator<Item = &LinkReportEntry> {
+        self.with_status(LinkStatus::Dead)
+    }
+
+    /// Files whose check failed
+    pub fn unchecked(&self) -> impl Iterator<Item = &LinkReportEntry> {
+        self.with_status(LinkStatus::Unchecked)
+    }
+
+    /// Total size of the files that still exist
+    pub fn total_size(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, LinkStatus::Alive | LinkStatus::PremiumOnly))
+            .map(|e| e.file_size)
+            .sum()
+    }
+
+    /// Export the report as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Export the report as csv, with one line per file
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "file_code,status,file_name,file_size,available_uts,error_code,error_message\n",
+        );
+
+        for entry in &self.entries {
+            let status = match entry.status {
+                LinkStatus::Alive => "alive",
+                LinkStatus::PremiumOnly => "premium_only",
+                LinkStatus::Dead => "dead",
+                LinkStatus::Unchecked => "unchecked",
+            };
+
+            csv.push_str(&format!(
+                "{},{status},{},{},{},{},{}\n",
+                csv_field(&entry.file_code),
+                csv_field(entry.file_name.as_deref().unwrap_or_default()),
+                entry.file_size,
+                entry.available_uts,
+                entry.error_code.map(|c| c.to_string()).unwrap_or_default(),
+                csv_field(entry.error_message.as_deref().unwrap_or_default()),
+            ));
+        }
+
+        csv
+    }
+
+    fn with_status(&self, status: LinkStatus) -> impl Iterator<Item = &LinkReportEntry> {
+        self.entries.iter().filter(move |e| e.status == status)
+    }
+}
+
+impl LinkReportEntry {
+    /// Entry of a file checked by the api
+    fn checked(file_code: &FileCode, result: Result<FileInfo, FileInfoError>) -> Self {
+        match result {
+            Ok(info) => Self {
+                file_code: file_code.code().to_string(),
+                status: match info.need_premium {
+                    true => LinkStatus::PremiumOnly,
+                    false => LinkStatus::Alive,
+                },
+                file_name: Some(info.file_name),
+                file_size: info.file_size,
+                available_uts: info.available_uts,
+                error_code: None,
+                error_message: None,
+            },
+            Err(error) => Self {
+                file_code: file_code.code().to_string(),
+                // The api returned nothing for the file, it was not checked
+                status: match error.code {
+                    FileInfoErrorCode::Missing => LinkStatus::Unchecked,
+                    _ => LinkStatus::Dead,
+                },
+                file_name: None,
+                file_size: 0,
+                available_uts: false,
+                error_code: error.code.api_code(),
+                error_message: Some(error.message),
+            },
+        }
+    }
+
+    /// Entry of a file whose request failed
+    fn unchecked(file_code: &FileCode, error: &Error) -> Self {
+        Self {
+            file_code: file_code.code().to_string(),
+            status: LinkStatus::Unchecked,
+            file_name: None,
+            file_size: 0,
+            available_uts: false,
+            error_code: error.api_status(),
+            error_message: Some(error.to_string()),
+        }
+    }
+}
+
+/// Link checker
+impl Uptobox {
+    /// Check the availability of a list of files
+    ///
+    /// The duplicate codes are checked once, and the codes are split in chunks which are checked in parallel.
+    /// The files of a chunk whose request failed, and the files missing from the responses, are reported as [`LinkStatus::Unchecked`]
+    pub async fn check_links(
+        &self,
+        file_codes: impl IntoIterator<Item = impl IntoFileCode>,
+        check_links: &CheckLinks,
+    ) -> UptoboxResult<LinkReport> {
+        let file_codes = file_codes
+            .into_iter()
+            .map(IntoFileCode::into_file_code)
+            .collect::<UptoboxResult<Vec<FileCode>>>()?;
+        let file_codes = dedup_file_codes(file_codes);
+
+        let chunks = futures::stream::iter(file_codes.chunks(check_links.chunk_size))
+            .map(|chunk| async move {
+                match self.get_files_informations_results(chunk).await {
+                    Ok(results) => results
+                        .into_iter()
+                        .map(|(file_code, result)| LinkReportEntry::checked(&file_code, result))
+                        .collect(),
+                    Err(error) => chunk
+                        .iter()
+                        .map(|file_code| LinkReportEntry::unchecked(file_code, &error))
+                        .collect::<Vec<_>>(),
+                }
+            })
+            .buffered(check_links.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(LinkReport {
+            entries: chunks.into_iter().flatten().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileInfoErrorCode;
+
+    fn file_code(code: &str) -> FileCode {
+        FileCode::parse(code).unwrap()
+    }
+
+    fn info(need_premium: bool) -> Result<FileInfo, FileInfoError> {
+        Ok(FileInfo {
+            file_code: "aaaaaaaaaaaa".into(),
+            file_name: "name".into(),
+            file_size: 10,
+            available_uts: true,
+            need_premium,
+        })
+    }
+
+    fn error(code: FileInfoErrorCode) -> Result<FileInfo, FileInfoError> {
+        Err(FileInfoError {
+            code,
+            message: "File not found".into(),
+        })
+    }
+
+    #[test]
+    fn status_mapping() {
+        let code = file_code("aaaaaaaaaaaa");
+
+        let alive = LinkReportEntry::checked(&code, info(false));
+        assert_eq!(alive.status, LinkStatus::Alive);
+        assert_eq!(alive.file_name.as_deref(), Some("name"));
+        assert_eq!(alive.file_size, 10);
+
+        let premium = LinkReportEntry::checked(&code, info(true));
+        assert_eq!(premium.status, LinkStatus::PremiumOnly);
+
+        let dead = LinkReportEntry::checked(&code, error(FileInfoErrorCode::NotFound));
+        assert_eq!(dead.status, LinkStatus::Dead);
+        assert_eq!(dead.error_code, Some(28));
+        assert_eq!(dead.error_message.as_deref(), Some("File not found"));
+
+        let missing = LinkReportEntry::checked(&code, error(FileInfoErrorCode::Missing));
+        assert_eq!(missing.status, LinkStatus::Unchecked);
+        assert_eq!(missing.error_code, None);
+
+        let unchecked =
+            LinkReportEntry::unchecked(&code, &Error::ParseResponse(1, "Error".into(), "".into()));
+        assert_eq!(unchecked.status, LinkStatus::Unchecked);
+        assert_eq!(unchecked.error_code, Some(1));
+    }
+
+    #[test]
+    fn report_totals() {
+        let code = file_code("aaaaaaaaaaaa");
+        let report = LinkReport {
+            entries: vec![
+                LinkReportEntry::checked(&code, info(false)),
+                LinkReportEntry::checked(&code, info(true)),
+                LinkReportEntry::checked(&code, error(FileInfoErrorCode::NotFound)),
+                LinkReportEntry::unchecked(&code, &Error::InvalidFileCode("".into())),
+            ],
+        };
+
+        assert_eq!(report.alive().count(), 1);
+        assert_eq!(report.premium_only().count(), 1);
+        assert_eq!(report.dead().count(), 1);
+        assert_eq!(report.unchecked().count(), 1);
+        assert_eq!(report.total_size(), 20);
+    }
+
+    #[test]
+    fn csv_escaping() {
+        let mut entry = LinkReportEntry::checked(&file_code("aaaaaaaaaaaa"), info(false));
+        entry.file_name = Some("a, \"quoted\"\nname".into());
+        let mut dead = LinkReportEntry::checked(
+            &file_code("bbbbbbbbbbbb"),
+            error(FileInfoErrorCode::Other(7)),
+        );
+        dead.error_message = Some("plain".into());
+        let report = LinkReport {
+            entries: vec![entry, dead],
+        };
+
+        assert_eq!(
+            report.to_csv(),
+            "file_code,status,file_name,file_size,available_uts,error_code,error_message\n\
+            aaaaaaaaaaaa,alive,\"a, \"\"quoted\"\"\nname\",10,true,,\n\
+            bbbbbbbbbbbb,dead,,0,false,7,plain\n"
+        );
+    }
+}
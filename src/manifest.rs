@@ -0,0 +1,215 @@
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{GetFilesFiles, Uptobox, UptoboxPath, UptoboxResult, Walk, WalkFolder};
+
+/// Version of the manifests written by this crate
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Tree of folders and files of an account, exported as json
+///
+/// The manifest holds the passwords of the files
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub version: u32,
+
+    /// Date of the export, in seconds since the unix epoch
+    pub taken_at: u64,
+
+    /// The first folder of the walk
+    pub root: UptoboxPath,
+    pub folders: Vec<ManifestFolder>,
+    pub files: Vec<ManifestFile>,
+}
+
+/// Folder of Manifest
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFolder {
+    pub path: UptoboxPath,
+    pub fld_id: usize,
+    pub hash: String,
+}
+
+/// File of Manifest
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ManifestFile {
+    /// The path of the folder of the file
+    pub path: UptoboxPath,
+    pub file: GetFilesFiles,
+}
+
+/// Differences between two manifests
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ManifestDiff {
+    pub added_folders: Vec<ManifestFolder>,
+    pub removed_folders: Vec<ManifestFolder>,
+    pub added: Vec<ManifestFile>,
+    pub removed: Vec<ManifestFile>,
+
+    /// Files with the same code and a new name
+    pub renamed: Vec<FileChange>,
+
+    /// Files with the same code and a new folder
+    pub moved: Vec<FileChange>,
+
+    /// Files with the same code and a new description, password, visibility or size
+    pub metadata_changed: Vec<FileChange>,
+}
+
+/// File of both manifests of ManifestDiff
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub before: ManifestFile,
+    pub after: ManifestFile,
+}
+
+impl Manifest {
+    /// Build the manifest from the folders of a walk, the first folder is the root
+    pub fn from_folders(folders: Vec<WalkFolder>) -> Self {
+        let taken_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let root = folders.first().map(|f| f.path.clone()).unwrap_or_default();
+
+        let mut manifest = Self {
+            version: MANIFEST_VERSION,
+            taken_at,
+            root,
+            folders: Vec::new(),
+            files: Vec::new(),
+        };
+        for folder in folders {
+            manifest.folders.push(ManifestFolder {
+                path: folder.path.clone(),
+                fld_id: folder.current_folder.fld_id,
+                hash: folder.current_folder.hash.clone(),
+            });
+            manifest
+                .files
+                .extend(folder.into_files().map(|f| ManifestFile {
+                    path: f.path,
+                    file: f.file,
+                }));
+        }
+
+        manifest
+    }
+
+    /// Export the manifest as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Read a manifest exported as json, the manifests of a newer version are rejected
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let manifest: Self = serde_json::from_str(json)?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported manifest version: {}",
+                manifest.version
+            )));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Compare this manifest with a newer one, the files are matched by code and the folders by path
+    pub fn diff(&self, newer: &Manifest) -> ManifestDiff {
+        let mut diff = ManifestDiff::default();
+
+        let before: HashMap<&UptoboxPath, &ManifestFolder> =
+            self.folders.iter().map(|f| (&f.path, f)).collect();
+        let after: HashMap<&UptoboxPath, &ManifestFolder> =
+            newer.folders.iter().map(|f| (&f.path, f)).collect();
+        diff.added_folders = newer
+            .folders
+            .iter()
+            .filter(|f| !before.contains_key(&f.path))
+            .cloned()
+            .collect();
+        diff.removed_folders = self
+            .folders
+            .iter()
+            .filter(|f| !after.contains_key(&f.path))
+            .cloned()
+            .collect();
+
+        let before: HashMap<&str, &ManifestFile> = self
+            .files
+            .iter()
+            .map(|f| (f.file.file_code.as_str(), f))
+            .collect();
+        let after: HashMap<&str, &ManifestFile> = newer
+            .files
+            .iter()
+            .map(|f| (f.file.file_code.as_str(), f))
+            .collect();
+        diff.removed = self
+            .files
+            .iter()
+            .filter(|f| !after.contains_key(f.file.file_code.as_str()))
+            .cloned()
+            .collect();
+
+        for file in &newer.files {
+            let old = match before.get(file.file.file_code.as_str()) {
+                Some(old) => *old,
+                None => {
+                    diff.added.push(file.clone());
+                    continue;
+                }
+            };
+            let change = || FileChange {
+                before: old.clone(),
+                after: file.clone(),
+            };
+
+            if old.file.file_name != file.file.file_name {
+                diff.renamed.push(change());
+            }
+            if old.path != file.path {
+                diff.moved.push(change());
+            }
+            if old.file.file_descr != file.file.file_descr
+                || old.file.file_password != file.file.file_password
+                || old.file.file_public != file.file.file_public
+                || old.file.file_size != file.file.file_size
+            {
+                diff.metadata_changed.push(change());
+            }
+        }
+
+        diff
+    }
+}
+
+impl ManifestDiff {
+    /// Check whether the manifests hold the same tree
+    pub fn is_empty(&self) -> bool {
+        self.added_folders.is_empty()
+            && self.removed_folders.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.moved.is_empty()
+            && self.metadata_changed.is_empty()
+    }
+
+    /// Export the differences as json
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Manifest
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and export them as a manifest
+    pub async fn export_manifest(&self, walk: &Walk) -> UptoboxResult<Manifest> {
+        let folders: Vec<WalkFolder> = self.walk(walk).try_collect().await?;
+
+        Ok(Manifest::from_folders(folders))
+    }
+}
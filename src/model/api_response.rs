@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+/// Response with its metadata
+#[derive(Debug)]
+pub struct ApiResponse<T> {
+    /// The data of the response
+    pub data: T,
+
+    /// The status code returned by the api
+    pub status_code: usize,
+
+    /// The message returned by the api
+    pub message: Option<String>,
+
+    /// The http headers of the response
+    pub headers: HeaderMap,
+
+    /// Time elapsed between the request and the end of the response
+    pub elapsed: Duration,
+
+    /// The fields ignored in lenient mode, see [`crate::Uptobox::lenient`]
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,195 @@
+use serde::de::{self, DeserializeOwned, Deserializer, Unexpected, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::fmt;
+
+thread_local! {
+    /// Warnings of the response being deserialized, `None` if the lenient mode is disabled
+    static WARNINGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Run a deserialization, and return the warnings recorded if the lenient mode is enabled
+pub(crate) fn with_lenient<T>(lenient: bool, f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    WARNINGS.with(|w| *w.borrow_mut() = lenient.then(Vec::new));
+    let result = f();
+    let warnings = WARNINGS.with(|w| w.borrow_mut().take()).unwrap_or_default();
+
+    (result, warnings)
+}
+
+/// Record a warning and return `None` in lenient mode, otherwise return the error
+pub(crate) fn lenient_fallback<T, E: de::Error>(error: String) -> Result<Option<T>, E> {
+    WARNINGS.with(|w| match w.borrow_mut().as_mut() {
+        Some(warnings) => {
+            warnings.push(error);
+            Ok(None)
+        }
+        None => Err(E::custom(error)),
+    })
+}
+
+/// Deserialize an optional field, a value of an unexpected type is `None` in lenient mode
+pub(crate) fn deserialize_lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match serde_json::from_value(value.clone()) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => lenient_fallback(format!("{e}, ignored value: {value}")),
+        },
+    }
+}
+
+/// Deserialize a date of the api as its raw string
+///
+/// A missing or null date is empty, a value of an unexpected type is empty in lenient mode
+pub(crate) fn deserialize_date<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(String::new()),
+        Some(Value::String(date)) => Ok(date),
+        Some(value) => {
+            lenient_fallback(format!("invalid date: {value}")).map(Option::unwrap_or_default)
+        }
+    }
+}
+
+/// Deserialize a bool from a bool, an integer or a string ("0", "1", "true", "false")
+pub(crate) fn deserialize_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(BoolVisitor)
+}
+
+struct BoolVisitor;
+
+impl<'de> Visitor<'de> for BoolVisitor {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a bool, an integer or a string")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<bool, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<bool, E> {
+        Ok(v != 0)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<bool, E> {
+        Ok(v != 0)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<bool, E> {
+        Ok(v != 0.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+        match v.trim().to_ascii_lowercase().as_str() {
+            "" | "0" | "false" | "no" | "off" => Ok(false),
+            "1" | "true" | "yes" | "on" => Ok(true),
+            _ => match v.trim().parse::<i64>() {
+                Ok(v) => Ok(v != 0),
+                Err(_) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GetFilesResponse;
+
+    #[derive(Deserialize, Debug)]
+    struct Lenient {
+        #[serde(default, deserialize_with = "deserialize_lenient")]
+        id: Option<usize>,
+        #[serde(default, deserialize_with = "deserialize_date")]
+        date: String,
+    }
+
+    fn parse<T: DeserializeOwned>(
+        lenient: bool,
+        json: &str,
+    ) -> (serde_json::Result<T>, Vec<String>) {
+        with_lenient(lenient, || serde_json::from_str(json))
+    }
+
+    #[test]
+    fn strict_mode() {
+        let (parsed, warnings) = parse::<Lenient>(false, r#"{"id":"abc"}"#);
+        assert!(parsed.is_err());
+        assert!(warnings.is_empty());
+
+        let (parsed, _) = parse::<Lenient>(false, r#"{"date":123}"#);
+        assert!(parsed
+            .unwrap_err()
+            .to_string()
+            .contains("invalid date: 123"));
+
+        let (parsed, warnings) =
+            parse::<Lenient>(false, r#"{"id":1,"date":"2023-01-31 12:00:00"}"#);
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.id, Some(1));
+        assert_eq!(parsed.date, "2023-01-31 12:00:00");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn lenient_mode() {
+        let (parsed, warnings) = parse::<Lenient>(true, r#"{"id":"abc","date":{"a":1}}"#);
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.id, None);
+        assert_eq!(parsed.date, "");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains(r#"ignored value: "abc""#));
+        assert!(warnings[1].contains("invalid date"));
+
+        // The warnings are not kept for the next deserialization
+        let (_, warnings) = parse::<Lenient>(false, r#"{"id":1}"#);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn missing_and_null_values() {
+        for json in ["{}", r#"{"id":null,"date":null}"#] {
+            let (parsed, warnings) = parse::<Lenient>(false, json);
+            let parsed = parsed.unwrap();
+            assert_eq!(parsed.id, None);
+            assert_eq!(parsed.date, "");
+            assert!(warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn extra_on_nested_model() {
+        let json = r#"{
+            "currentFolder": {"fileCount": 1, "fld_id": 1, "fld_parent_id": null, "hash": "h",
+                "totalFileSize": 10, "color": "red"},
+            "folders": [],
+            "files": [{"file_code": "abcdef123456", "file_created": "2023-01-31 12:00:00",
+                "file_descr": "", "file_downloads": 0, "file_last_download": null,
+                "file_name": "a.txt", "file_password": "", "file_public": 0, "file_size": 10,
+                "last_stream": null, "nb_stream": 0, "transcoded": null, "file_md5": "abc"}],
+            "pageCount": 1,
+            "totalFileCount": 1,
+            "totalFileSize": 10
+        }"#;
+        let (parsed, _) = parse::<GetFilesResponse>(false, json);
+        let parsed = parsed.unwrap();
+
+        assert_eq!(parsed.current_folder.extra["color"], "red");
+        assert_eq!(parsed.files[0].extra["file_md5"], "abc");
+        assert!(parsed.extra.is_empty());
+    }
+}
@@ -32,19 +32,37 @@ pub(crate) struct GenericEmpyDataResponseWrapper {
 }
 
 impl DeserializeCheck for GenericMessageResponseWrapper {
+    type Data = String;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
 }
 
 impl DeserializeCheck for GenericUpdatedResponseWrapper {
+    type Data = usize;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data.updated)
+    }
 }
 
 impl DeserializeCheck for GenericEmpyDataResponseWrapper {
+    type Data = ();
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, ())
+    }
 }
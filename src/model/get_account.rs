@@ -1,6 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
 
-use crate::util::DeserializeCheck;
+use crate::util::{DeserializeCheck, REDACTED};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -11,36 +13,63 @@ pub(crate) struct GetAccountResponseWrapper {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct GetAccountResponse {
-    #[serde(deserialize_with = "deserialize_usize_to_bool")]
+    #[serde(deserialize_with = "crate::model::de::deserialize_bool")]
     pub premium: bool,
     pub login: String,
     pub email: String,
     pub point: f32,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub premium_expire: String,
-    #[serde(deserialize_with = "deserialize_usize_to_bool")]
+    #[serde(deserialize_with = "crate::model::de::deserialize_bool")]
     #[serde(rename = "securityLock")]
     pub security_lock: bool,
-    #[serde(deserialize_with = "deserialize_usize_to_bool")]
+    #[serde(deserialize_with = "crate::model::de::deserialize_bool")]
     #[serde(rename = "directDownload")]
     pub direct_download: bool,
-    #[serde(deserialize_with = "deserialize_usize_to_bool")]
+    #[serde(deserialize_with = "crate::model::de::deserialize_bool")]
     #[serde(rename = "sslDownload")]
     pub ssl_download: bool,
     pub token: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GetAccountResponse {
+    /// The expiration date of the premium, `None` if it is empty or invalid
+    #[cfg(feature = "chrono")]
+    pub fn premium_expire_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.premium_expire)
+    }
+}
+
+impl fmt::Debug for GetAccountResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetAccountResponse")
+            .field("premium", &self.premium)
+            .field("login", &self.login)
+            .field("email", &self.email)
+            .field("point", &self.point)
+            .field("premium_expire", &self.premium_expire)
+            .field("security_lock", &self.security_lock)
+            .field("direct_download", &self.direct_download)
+            .field("ssl_download", &self.ssl_download)
+            .field("token", &REDACTED)
+            .field("extra", &self.extra)
+            .finish()
+    }
 }
 
 impl DeserializeCheck for GetAccountResponseWrapper {
+    type Data = GetAccountResponse;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
-}
 
-fn deserialize_usize_to_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let usize_value = usize::deserialize(deserializer)?;
-    Ok(usize_value != 0)
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
 }
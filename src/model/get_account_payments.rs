@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::util::DeserializeCheck;
 
@@ -16,18 +17,110 @@ pub(crate) struct GetAccountPaymentsListResponseWrapper {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetAccountPaymentsResponse {
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub created: String,
-    pub status: String,
+    pub status: PaymentStatus,
     pub amount: String,
     pub days: usize,
     #[serde(rename = "type")]
-    pub _type: String,
+    pub _type: PaymentType,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GetAccountPaymentsResponse {
+    /// The date of the payment, `None` if it is empty or invalid
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.created)
+    }
+}
+
+/// Status of a payment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum PaymentStatus {
+    Paid,
+    Pending,
+    Canceled,
+    Refunded,
+    /// Any other status returned by the api, kept as is
+    Unknown(String),
+}
+
+impl From<String> for PaymentStatus {
+    fn from(status: String) -> Self {
+        // Only the exact values are mapped, so that serializing gives back the value of the api
+        match status.as_str() {
+            "paid" => PaymentStatus::Paid,
+            "pending" => PaymentStatus::Pending,
+            "canceled" => PaymentStatus::Canceled,
+            "refunded" => PaymentStatus::Refunded,
+            _ => PaymentStatus::Unknown(status),
+        }
+    }
+}
+
+impl From<PaymentStatus> for String {
+    fn from(status: PaymentStatus) -> Self {
+        match status {
+            PaymentStatus::Paid => "paid".into(),
+            PaymentStatus::Pending => "pending".into(),
+            PaymentStatus::Canceled => "canceled".into(),
+            PaymentStatus::Refunded => "refunded".into(),
+            PaymentStatus::Unknown(status) => status,
+        }
+    }
+}
+
+/// Type of a payment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum PaymentType {
+    Paypal,
+    CreditCard,
+    Crypto,
+    Points,
+    /// Any other type returned by the api, kept as is
+    Unknown(String),
+}
+
+impl From<String> for PaymentType {
+    fn from(_type: String) -> Self {
+        // Only the exact values are mapped, so that serializing gives back the value of the api
+        match _type.as_str() {
+            "paypal" => PaymentType::Paypal,
+            "card" => PaymentType::CreditCard,
+            "crypto" => PaymentType::Crypto,
+            "points" => PaymentType::Points,
+            _ => PaymentType::Unknown(_type),
+        }
+    }
+}
+
+impl From<PaymentType> for String {
+    fn from(_type: PaymentType) -> Self {
+        match _type {
+            PaymentType::Paypal => "paypal".into(),
+            PaymentType::CreditCard => "card".into(),
+            PaymentType::Crypto => "crypto".into(),
+            PaymentType::Points => "points".into(),
+            PaymentType::Unknown(_type) => _type,
+        }
+    }
 }
 
 impl DeserializeCheck for GetAccountPaymentsResponseWrapper {
+    type Data = Vec<GetAccountPaymentsResponse>;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data.list)
+    }
 }
@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::util::DeserializeCheck;
 
@@ -11,7 +12,7 @@ pub(crate) struct GetDownloadUrlResponseWrapper {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum GetDownloadUrlResponse {
     Link(GetDownloadUrlLink),
@@ -19,22 +20,35 @@ pub enum GetDownloadUrlResponse {
 }
 
 /// Data of GetDownloadUrlResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDownloadUrlLink {
     pub dl_link: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Data of GetDownloadUrlResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDownloadUrlWait {
     pub waiting: usize,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_lenient")]
     pub waiting_token: Option<String>,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl DeserializeCheck for GetDownloadUrlResponseWrapper {
+    type Data = GetDownloadUrlResponse;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
 }
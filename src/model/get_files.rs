@@ -1,6 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
 
-use crate::util::DeserializeCheck;
+use crate::model::de::{deserialize_bool, deserialize_lenient};
+use crate::util::{DeserializeCheck, REDACTED};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -11,7 +14,7 @@ pub(crate) struct GetFilesResponseWrapper {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFilesResponse {
     pub current_folder: GetFilesCurrentFolder,
@@ -20,49 +23,193 @@ pub struct GetFilesResponse {
     pub page_count: usize,
     pub total_file_count: usize,
     pub total_file_size: usize,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Data of GetFilesResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetFilesCurrentFolder {
     #[serde(rename = "fileCount")]
     pub file_count: usize,
     pub fld_id: usize,
     #[serde(default = "default_string")]
     pub fld_name: String,
+    #[serde(default, deserialize_with = "deserialize_lenient")]
     pub fld_parent_id: Option<usize>,
     pub hash: String,
     #[serde(default = "default_string")]
     pub name: String,
     #[serde(rename = "totalFileSize")]
     pub total_file_size: usize,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Data of GetFilesResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetFilesFolders {
     pub fld_id: usize,
     pub fld_name: String,
     pub hash: String,
     pub name: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Data of GetFilesResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct GetFilesFiles {
     pub file_code: String,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub file_created: String,
     pub file_descr: String,
     pub file_downloads: usize,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub file_last_download: String,
     pub file_name: String,
-    pub file_password: String,
-    pub file_public: usize,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_password",
+        serialize_with = "serialize_password"
+    )]
+    pub file_password: Option<String>,
+    pub file_public: FileVisibility,
     pub file_size: usize,
+    #[serde(default, deserialize_with = "deserialize_lenient")]
     pub id: Option<usize>,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub last_stream: String,
     pub nb_stream: usize,
-    pub transcoded: Option<usize>,
+    #[serde(default)]
+    pub transcoded: TranscodingState,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GetFilesFiles {
+    /// The file is protected by a password
+    pub fn has_password(&self) -> bool {
+        self.file_password.is_some()
+    }
+
+    /// The file is public
+    pub fn is_public(&self) -> bool {
+        self.file_public == FileVisibility::Public
+    }
+
+    /// The creation date, `None` if it is empty or invalid
+    #[cfg(feature = "chrono")]
+    pub fn file_created_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.file_created)
+    }
+
+    /// The last download date, `None` if the file was never downloaded
+    #[cfg(feature = "chrono")]
+    pub fn file_last_download_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.file_last_download)
+    }
+
+    /// The last stream date, `None` if the file was never streamed
+    #[cfg(feature = "chrono")]
+    pub fn last_stream_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.last_stream)
+    }
+}
+
+impl fmt::Debug for GetFilesFiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetFilesFiles")
+            .field("file_code", &self.file_code)
+            .field("file_created", &self.file_created)
+            .field("file_descr", &self.file_descr)
+            .field("file_downloads", &self.file_downloads)
+            .field("file_last_download", &self.file_last_download)
+            .field("file_name", &self.file_name)
+            .field(
+                "file_password",
+                &self.file_password.as_ref().map(|_| REDACTED),
+            )
+            .field("file_public", &self.file_public)
+            .field("file_size", &self.file_size)
+            .field("id", &self.id)
+            .field("last_stream", &self.last_stream)
+            .field("nb_stream", &self.nb_stream)
+            .field("transcoded", &self.transcoded)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+/// Visibility of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileVisibility {
+    Private,
+    Public,
+}
+
+impl<'de> Deserialize<'de> for FileVisibility {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserialize_bool(deserializer)? {
+            true => Ok(FileVisibility::Public),
+            false => Ok(FileVisibility::Private),
+        }
+    }
+}
+
+impl Serialize for FileVisibility {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FileVisibility::Private => serializer.serialize_u64(0),
+            FileVisibility::Public => serializer.serialize_u64(1),
+        }
+    }
+}
+
+/// Transcoding state of a file on uptostream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TranscodingState {
+    /// The file can not be transcoded
+    #[default]
+    Unavailable,
+    NotTranscoded,
+    Transcoded,
+    /// Any other value returned by the api
+    Unknown(usize),
+}
+
+impl<'de> Deserialize<'de> for TranscodingState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match deserialize_lenient::<_, usize>(deserializer)? {
+            None => TranscodingState::Unavailable,
+            Some(0) => TranscodingState::NotTranscoded,
+            Some(1) => TranscodingState::Transcoded,
+            Some(state) => TranscodingState::Unknown(state),
+        })
+    }
+}
+
+impl Serialize for TranscodingState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TranscodingState::Unavailable => serializer.serialize_none(),
+            TranscodingState::NotTranscoded => serializer.serialize_some(&0),
+            TranscodingState::Transcoded => serializer.serialize_some(&1),
+            TranscodingState::Unknown(state) => serializer.serialize_some(state),
+        }
+    }
+}
+
+/// Empty passwords are `None`
+fn deserialize_password<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_lenient::<_, String>(deserializer)?.filter(|p| !p.is_empty()))
 }
 
 fn default_string() -> String {
@@ -70,7 +217,21 @@ fn default_string() -> String {
 }
 
 impl DeserializeCheck for GetFilesResponseWrapper {
+    type Data = GetFilesResponse;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
+}
+
+/// `None` is serialized as an empty password, like the api does
+fn serialize_password<S>(password: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(password.as_deref().unwrap_or_default())
 }
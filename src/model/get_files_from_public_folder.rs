@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::util::DeserializeCheck;
 
@@ -17,15 +18,33 @@ pub(crate) struct GetFilesFromPublicFolderListResponse {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetFilesFromPublicFolderResponse {
     pub file_name: String,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_date")]
     pub file_date_inserted: String,
     pub file_code: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl GetFilesFromPublicFolderResponse {
+    /// The upload date, `None` if it is empty or invalid
+    #[cfg(feature = "chrono")]
+    pub fn file_date_inserted_at(&self) -> Option<chrono::NaiveDateTime> {
+        crate::model::timestamp::parse_opt(&self.file_date_inserted)
+    }
 }
 
 impl DeserializeCheck for GetFilesFromPublicFolderResponseWrapper {
+    type Data = Vec<GetFilesFromPublicFolderResponse>;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data.list)
+    }
 }
@@ -1,6 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 
 use crate::util::DeserializeCheck;
+use crate::FileCode;
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -16,25 +19,193 @@ pub(crate) struct GetFilesInformationsListResponse {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetFilesInformationsResponse {
     pub file_code: String,
     pub file_name: String,
     pub file_size: usize,
     pub available_uts: bool,
     pub need_premium: bool,
+    #[serde(default, deserialize_with = "crate::model::de::deserialize_lenient")]
     pub error: Option<GetFilesInformationsError>,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Data of GetFilesInformationsResponse
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GetFilesInformationsError {
     pub code: usize,
     pub message: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Informations of an available file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileInfo {
+    pub file_code: String,
+    pub file_name: String,
+    pub file_size: usize,
+    pub available_uts: bool,
+    pub need_premium: bool,
+}
+
+/// Error of an unavailable file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileInfoError {
+    pub code: FileInfoErrorCode,
+    pub message: String,
+}
+
+/// Code of FileInfoError
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileInfoErrorCode {
+    /// The file does not exist
+    NotFound,
+
+    /// The file is missing from the response
+    Missing,
+
+    /// Any other code returned by the api
+    Other(usize),
+}
+
+const FILE_NOT_FOUND: usize = 28;
+
+impl FileInfoErrorCode {
+    /// The code returned by the api
+    pub fn api_code(&self) -> Option<usize> {
+        match self {
+            FileInfoErrorCode::NotFound => Some(FILE_NOT_FOUND),
+            FileInfoErrorCode::Missing => None,
+            FileInfoErrorCode::Other(code) => Some(*code),
+        }
+    }
+}
+
+impl From<usize> for FileInfoErrorCode {
+    fn from(code: usize) -> Self {
+        match code {
+            FILE_NOT_FOUND => FileInfoErrorCode::NotFound,
+            code => FileInfoErrorCode::Other(code),
+        }
+    }
+}
+
+impl From<GetFilesInformationsResponse> for Result<FileInfo, FileInfoError> {
+    fn from(response: GetFilesInformationsResponse) -> Self {
+        match response.error {
+            Some(error) => Err(FileInfoError {
+                code: error.code.into(),
+                message: error.message,
+            }),
+            None => Ok(FileInfo {
+                file_code: response.file_code,
+                file_name: response.file_name,
+                file_size: response.file_size,
+                available_uts: response.available_uts,
+                need_premium: response.need_premium,
+            }),
+        }
+    }
+}
+
+/// Match the informations returned by the api with the file codes, a code may appear several times
+pub(crate) fn match_informations(
+    file_codes: Vec<FileCode>,
+    informations: Vec<GetFilesInformationsResponse>,
+) -> Vec<(FileCode, Result<FileInfo, FileInfoError>)> {
+    let informations: HashMap<String, Result<FileInfo, FileInfoError>> = informations
+        .into_iter()
+        .map(|information| (information.file_code.clone(), information.into()))
+        .collect();
+
+    file_codes
+        .into_iter()
+        .map(|file_code| {
+            let result = match informations.get(file_code.code()) {
+                Some(result) => result.clone(),
+                None => Err(FileInfoError {
+                    code: FileInfoErrorCode::Missing,
+                    message: "Missing from the response".into(),
+                }),
+            };
+
+            (file_code, result)
+        })
+        .collect()
 }
 
 impl DeserializeCheck for GetFilesInformationsResponseWrapper {
+    type Data = Vec<GetFilesInformationsResponse>;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(file_code: &str, error: Option<usize>) -> GetFilesInformationsResponse {
+        GetFilesInformationsResponse {
+            file_code: file_code.into(),
+            file_name: "name".into(),
+            file_size: 10,
+            available_uts: false,
+            need_premium: false,
+            error: error.map(|code| GetFilesInformationsError {
+                code,
+                message: "error".into(),
+                extra: Map::new(),
+            }),
+            extra: Map::new(),
+        }
+    }
+
+    #[test]
+    fn duplicates_and_missing() {
+        let codes = [
+            "aaaaaaaaaaaa",
+            "bbbbbbbbbbbb",
+            "aaaaaaaaaaaa",
+            "cccccccccccc",
+        ];
+        let results = match_informations(
+            codes.iter().map(|c| FileCode::parse(c).unwrap()).collect(),
+            vec![
+                response("bbbbbbbbbbbb", Some(28)),
+                response("aaaaaaaaaaaa", None),
+            ],
+        );
+
+        let codes: Vec<&str> = results.iter().map(|(c, _)| c.code()).collect();
+        assert_eq!(
+            codes,
+            [
+                "aaaaaaaaaaaa",
+                "bbbbbbbbbbbb",
+                "aaaaaaaaaaaa",
+                "cccccccccccc"
+            ]
+        );
+        assert!(results[0].1.is_ok());
+        assert_eq!(
+            results[1].1.as_ref().unwrap_err().code,
+            FileInfoErrorCode::NotFound
+        );
+        assert_eq!(results[2].1, results[0].1);
+        assert_eq!(
+            results[3].1.as_ref().unwrap_err().code,
+            FileInfoErrorCode::Missing
+        );
+    }
 }
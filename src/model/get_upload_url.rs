@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::util::DeserializeCheck;
 
@@ -11,15 +12,24 @@ pub(crate) struct GetUploadUrlResponseWrapper {
 }
 
 /// Response
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUploadUrlResponse {
     pub upload_link: String,
     pub max_upload: String,
+    /// Fields unknown to the crate
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl DeserializeCheck for GetUploadUrlResponseWrapper {
+    type Data = GetUploadUrlResponse;
+
     fn status_code(&self) -> usize {
         self.status_code
     }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
 }
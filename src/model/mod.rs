@@ -1,3 +1,5 @@
+pub mod api_response;
+pub(crate) mod de;
 pub mod generic;
 pub mod get_account;
 pub mod get_account_payments;
@@ -6,3 +8,6 @@ pub mod get_files;
 pub mod get_files_from_public_folder;
 pub mod get_files_informations;
 pub mod get_upload_url;
+pub mod raw;
+#[cfg(feature = "chrono")]
+pub(crate) mod timestamp;
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::util::DeserializeCheck;
+use crate::ApiResponse;
+
+/// Response
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RawResponse {
+    pub status_code: usize,
+    #[serde(default)]
+    pub data: Value,
+    pub message: Option<String>,
+}
+
+impl From<ApiResponse<Value>> for RawResponse {
+    fn from(response: ApiResponse<Value>) -> Self {
+        Self {
+            status_code: response.status_code,
+            data: response.data,
+            message: response.message,
+        }
+    }
+}
+
+impl DeserializeCheck for RawResponse {
+    type Data = Value;
+
+    fn status_code(&self) -> usize {
+        self.status_code
+    }
+
+    fn into_parts(self) -> (Option<String>, Self::Data) {
+        (self.message, self.data)
+    }
+}
@@ -0,0 +1,56 @@
+//! Dates returned by the api (eg. `2023-01-31 12:00:00`)
+//!
+//! The raw strings are kept in the models, and parsed by their `_at` accessors with the `chrono` feature
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+/// Format of the dates of the api
+const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Date returned by the api when there is no date
+const ZERO: &str = "0000-00-00 00:00:00";
+
+/// Parse a date of the api, the empty or zero dates are `None`
+pub(crate) fn parse(date: &str) -> chrono::ParseResult<Option<NaiveDateTime>> {
+    let date = date.trim();
+    if date.is_empty() || date.starts_with(&ZERO[..10]) {
+        return Ok(None);
+    }
+
+    NaiveDateTime::parse_from_str(date, FORMAT)
+        .or_else(|_| DateTime::parse_from_rfc3339(date).map(|d| d.naive_utc()))
+        .or_else(|e| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .ok_or(e)
+        })
+        .map(Some)
+}
+
+/// Parse a date of the api, the empty, zero or invalid dates are `None`
+pub(crate) fn parse_opt(date: &str) -> Option<NaiveDateTime> {
+    parse(date).ok().flatten()
+}
+
+/// Serialize a date in the format of the api
+pub(crate) fn serialize<S>(date: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_str(&date.format(FORMAT))
+}
+
+/// Deserialize a date in the format of the api
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let date = String::deserialize(deserializer)?;
+    parse(&date)
+        .ok()
+        .flatten()
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid date: {date}")))
+}
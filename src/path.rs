@@ -0,0 +1,138 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::GetFilesFolders;
+
+/// Path of a folder on uptobox (eg. `//dev/builds`)
+///
+/// The path is normalized when it is parsed: the duplicate and trailing slashes are removed, and `.` and `..` are resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UptoboxPath {
+    segments: Vec<String>,
+}
+
+impl UptoboxPath {
+    /// The root folder
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Parse and normalize a path
+    pub fn parse(path: &str) -> Self {
+        Self::root().join(path)
+    }
+
+    /// Join a relative path, the `..` segments can not go above the root folder
+    pub fn join(&self, path: impl AsRef<str>) -> Self {
+        let mut segments = self.segments.clone();
+        for segment in path.as_ref().split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    segments.pop();
+                }
+                segment => segments.push(segment.to_string()),
+            }
+        }
+
+        Self { segments }
+    }
+
+    /// The path is the root folder
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The parent folder, `None` for the root folder
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.segments.split_last()?;
+        Some(Self {
+            segments: parent.to_vec(),
+        })
+    }
+
+    /// The name of the folder, `None` for the root folder
+    pub fn file_name(&self) -> Option<&str> {
+        self.segments.last().map(String::as_str)
+    }
+
+    /// The names of the folders from the root folder
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().map(String::as_str)
+    }
+
+    /// Number of folders between the root folder and this folder
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// The path is inside `base`, or is `base`
+    pub fn starts_with(&self, base: &UptoboxPath) -> bool {
+        self.segments.starts_with(&base.segments)
+    }
+
+    /// The form expected by the listing of the files (`///` for the root folder, `//dev/builds` otherwise)
+    pub fn to_listing_path(&self) -> String {
+        match self.is_root() {
+            true => "///".into(),
+            false => self.to_string(),
+        }
+    }
+
+    /// The form expected by the creation of a folder (`//` for the root folder, `//dev/builds` otherwise)
+    pub fn to_folder_path(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl GetFilesFolders {
+    /// Path of the folder, from the path of the folder listed
+    pub fn path(&self, parent: &UptoboxPath) -> UptoboxPath {
+        match self.fld_name.starts_with("//") {
+            true => UptoboxPath::parse(&self.fld_name),
+            false => parent.join(&self.name),
+        }
+    }
+}
+
+impl fmt::Display for UptoboxPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "//{}", self.segments.join("/"))
+    }
+}
+
+impl From<&str> for UptoboxPath {
+    fn from(path: &str) -> Self {
+        Self::parse(path)
+    }
+}
+
+impl From<String> for UptoboxPath {
+    fn from(path: String) -> Self {
+        Self::parse(&path)
+    }
+}
+
+impl From<&String> for UptoboxPath {
+    fn from(path: &String) -> Self {
+        Self::parse(path)
+    }
+}
+
+impl From<&UptoboxPath> for UptoboxPath {
+    fn from(path: &UptoboxPath) -> Self {
+        path.clone()
+    }
+}
+
+impl Serialize for UptoboxPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UptoboxPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::parse(&String::deserialize(deserializer)?))
+    }
+}
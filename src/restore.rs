@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::util::{BATCH_SIZE, REDACTED};
+use crate::{Manifest, ManifestFile, UpdateFile, Uptobox, UptoboxPath, UptoboxResult, Walk};
+
+/// Change of the metadata of a file
+#[derive(Clone, PartialEq, Eq)]
+pub enum MetadataChange {
+    Name(String),
+    Description(String),
+
+    /// The new password, empty to remove it
+    Password(String),
+    Public(bool),
+}
+
+/// Change applied by a restore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreAction {
+    /// Create a folder, its parent exists or is created by a previous action
+    CreateFolder { path: UptoboxPath },
+
+    /// Move a file back to its folder
+    MoveFile {
+        file_code: String,
+        from: UptoboxPath,
+        to: UptoboxPath,
+    },
+
+    /// Reapply the metadata of a file
+    UpdateFile {
+        file_code: String,
+        changes: Vec<MetadataChange>,
+    },
+}
+
+/// Changes needed to restore an account to a manifest
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RestorePlan {
+    /// The actions, in the order they are applied
+    pub actions: Vec<RestoreAction>,
+
+    /// Files of the manifest missing from the account, they can not be restored
+    pub missing_files: Vec<ManifestFile>,
+}
+
+impl Manifest {
+    /// Compute the changes needed to restore a tree to this manifest
+    ///
+    /// `current` is a manifest of the tree as it is now, the files are matched by code
+    pub fn restore_plan(&self, current: &Manifest) -> RestorePlan {
+        let mut plan = RestorePlan::default();
+
+        let mut existing: Vec<&UptoboxPath> = current.folders.iter().map(|f| &f.path).collect();
+        let mut folders: Vec<&UptoboxPath> = self.folders.iter().map(|f| &f.path).collect();
+        folders.sort_by_key(|path| path.depth());
+        for path in folders {
+            if !existing.contains(&path) {
+                plan.actions
+                    .push(RestoreAction::CreateFolder { path: path.clone() });
+                existing.push(path);
+            }
+        }
+
+        let current: HashMap<&str, &ManifestFile> = current
+            .files
+            .iter()
+            .map(|f| (f.file.file_code.as_str(), f))
+            .collect();
+        let mut updates = Vec::new();
+        for target in &self.files {
+            let file_code = &target.file.file_code;
+            let now = match current.get(file_code.as_str()) {
+                Some(now) => now,
+                None => {
+                    plan.missing_files.push(target.clone());
+                    continue;
+                }
+            };
+
+            if now.path != target.path {
+                plan.actions.push(RestoreAction::MoveFile {
+                    file_code: file_code.clone(),
+                    from: now.path.clone(),
+                    to: target.path.clone(),
+                });
+            }
+
+            let (now, target) = (&now.file, &target.file);
+            let mut changes = Vec::new();
+            if now.file_name != target.file_name {
+                changes.push(MetadataChange::Name(target.file_name.clone()));
+            }
+            if now.file_descr != target.file_descr {
+                changes.push(MetadataChange::Description(target.file_descr.clone()));
+            }
+            if now.file_password != target.file_password {
+                changes.push(MetadataChange::Password(
+                    target.file_password.clone().unwrap_or_default(),
+                ));
+            }
+            if now.file_public != target.file_public {
+                changes.push(MetadataChange::Public(target.is_public()));
+            }
+            if !changes.is_empty() {
+                updates.push(RestoreAction::UpdateFile {
+                    file_code: file_code.clone(),
+                    changes,
+                });
+            }
+        }
+        plan.actions.extend(updates);
+
+        plan
+    }
+}
+
+impl RestorePlan {
+    /// Check whether the tree already matches the manifest
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl fmt::Display for RestorePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for action in &self.actions {
+            match action {
+                RestoreAction::CreateFolder { path } => writeln!(f, "create {path}")?,
+                RestoreAction::MoveFile {
+                    file_code,
+                    from,
+                    to,
+                } => writeln!(f, "move {file_code} from {from} to {to}")?,
+                RestoreAction::UpdateFile { file_code, changes } => {
+                    write!(f, "update {file_code}:")?;
+                    for change in changes {
+                        match change {
+                            MetadataChange::Name(name) => write!(f, " name={name:?}")?,
+                            MetadataChange::Description(descr) => {
+                                write!(f, " description={descr:?}")?
+                            }
+                            MetadataChange::Password(password) if password.is_empty() => {
+                                write!(f, " password=\"\"")?
+                            }
+                            MetadataChange::Password(_) => write!(f, " password={REDACTED}")?,
+                            MetadataChange::Public(public) => write!(f, " public={public}")?,
+                        }
+                    }
+                    writeln!(f)?;
+                }
+            }
+        }
+        for file in &self.missing_files {
+            writeln!(
+                f,
+                "missing {} ({})",
+                file.path.join(&file.file.file_name),
+                file.file.file_code
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for MetadataChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => f.debug_tuple("Name").field(name).finish(),
+            Self::Description(descr) => f.debug_tuple("Description").field(descr).finish(),
+            Self::Password(password) => f
+                .debug_tuple("Password")
+                .field(&match password.is_empty() {
+                    true => "",
+                    false => REDACTED,
+                })
+                .finish(),
+            Self::Public(public) => f.debug_tuple("Public").field(public).finish(),
+        }
+    }
+}
+
+/// Restore
+impl Uptobox {
+    /// Compute the changes needed to restore the account to a manifest, without applying them
+    ///
+    /// The whole account is walked, to find the files moved out of the root of the manifest
+    pub async fn restore_plan(&self, manifest: &Manifest) -> UptoboxResult<RestorePlan> {
+        let current = self
+            .export_manifest(&Walk::new(UptoboxPath::root()))
+            .await?;
+
+        Ok(manifest.restore_plan(&current))
+    }
+
+    /// Apply the changes of a restore plan, and return the number of actions applied
+    pub async fn execute_restore_plan(&self, plan: &RestorePlan) -> UptoboxResult<usize> {
+        let mut applied = 0;
+
+        let mut moves: BTreeMap<&UptoboxPath, Vec<&str>> = BTreeMap::new();
+        for action in &plan.actions {
+            match action {
+                RestoreAction::CreateFolder { path } => {
+                    let parent = path.parent().unwrap_or_default();
+                    let name = path.file_name().unwrap_or_default();
+                    self.create_folder(parent, name).await?;
+                    applied += 1;
+                }
+                RestoreAction::MoveFile { file_code, to, .. } => {
+                    moves.entry(to).or_default().push(file_code);
+                }
+                RestoreAction::UpdateFile { .. } => {}
+            }
+        }
+
+        for (path, codes) in moves {
+            let fld_id = self.folder_id(path).await?;
+            for batch in codes.chunks(BATCH_SIZE) {
+                self.move_files(batch.iter().copied(), fld_id).await?;
+                applied += batch.len();
+            }
+        }
+
+        for action in &plan.actions {
+            if let RestoreAction::UpdateFile { file_code, changes } = action {
+                let mut update_file = UpdateFile::new(file_code)?;
+                for change in changes {
+                    match change {
+                        MetadataChange::Name(name) => update_file.name(name),
+                        MetadataChange::Description(descr) => update_file.description(descr),
+                        MetadataChange::Password(password) => update_file.password(password),
+                        MetadataChange::Public(public) => update_file.public(*public),
+                    };
+                }
+                self.update_file(&update_file).await?;
+                applied += 1;
+            }
+        }
+
+        Ok(applied)
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use futures::TryStreamExt;
+
+use crate::util::BATCH_SIZE;
+use crate::{GetFilesFiles, Uptobox, UptoboxPath, UptoboxResult, Walk, WalkFile};
+
+/// Condition for a file to be stale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionRule {
+    /// Not downloaded nor streamed for this duration, the files never used are checked against their creation date
+    Inactive(Duration),
+
+    /// Never downloaded and created before this duration
+    NeverDownloaded(Duration),
+}
+
+/// Retention policy, a file is stale when any rule matches
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    rules: Vec<RetentionRule>,
+    now: Option<NaiveDateTime>,
+}
+
+/// Stale file, with the first rule it matches
+#[derive(Debug, Clone)]
+pub struct RetentionMatch {
+    pub file: WalkFile,
+    pub rule: RetentionRule,
+}
+
+/// Stale files of a walk
+#[derive(Debug, Clone, Default)]
+pub struct RetentionReport {
+    pub files: Vec<RetentionMatch>,
+}
+
+impl RetentionRule {
+    /// Check whether a file matches the rule at a given date
+    pub fn matches(&self, file: &GetFilesFiles, now: NaiveDateTime) -> bool {
+        match *self {
+            Self::Inactive(duration) => {
+                let last_activity = file
+                    .file_last_download_at()
+                    .max(file.last_stream_at())
+                    .or_else(|| file.file_created_at());
+                last_activity.is_some_and(|date| date <= now - duration)
+            }
+            Self::NeverDownloaded(duration) => {
+                file.file_downloads == 0
+                    && file
+                        .file_created_at()
+                        .is_some_and(|date| date <= now - duration)
+            }
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Create a new instance, matching no file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn rule(&mut self, rule: RetentionRule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Match the files not downloaded nor streamed for a number of days
+    pub fn inactive_days(&mut self, days: i64) -> &mut Self {
+        self.rule(RetentionRule::Inactive(Duration::days(days)))
+    }
+
+    /// Match the files never downloaded and created more than a number of days ago
+    pub fn never_downloaded_days(&mut self, days: i64) -> &mut Self {
+        self.rule(RetentionRule::NeverDownloaded(Duration::days(days)))
+    }
+
+    /// Set the date the rules are checked at, the current UTC date by default
+    pub fn now(&mut self, now: NaiveDateTime) -> &mut Self {
+        let _ = self.now.insert(now);
+        self
+    }
+
+    /// Return the first rule matching a file
+    pub fn check(&self, file: &GetFilesFiles) -> Option<RetentionRule> {
+        let now = self.now.unwrap_or_else(|| Utc::now().naive_utc());
+
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(file, now))
+            .copied()
+    }
+}
+
+impl RetentionReport {
+    /// Select the stale files
+    pub fn from_files(files: Vec<WalkFile>, policy: &RetentionPolicy) -> Self {
+        let files = files
+            .into_iter()
+            .filter_map(|file| {
+                policy
+                    .check(&file.file)
+                    .map(|rule| RetentionMatch { file, rule })
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// Size of the stale files
+    pub fn total_size(&self) -> usize {
+        self.files.iter().map(|m| m.file.file.file_size).sum()
+    }
+}
+
+/// Retention
+impl Uptobox {
+    /// Walk through a folder and its subfolders, and select the stale files
+    pub async fn retention_report(
+        &self,
+        walk: &Walk,
+        policy: &RetentionPolicy,
+    ) -> UptoboxResult<RetentionReport> {
+        let files: Vec<WalkFile> = self.walk_files(walk).try_collect().await?;
+
+        Ok(RetentionReport::from_files(files, policy))
+    }
+
+    /// Move the stale files to an archive folder, created if missing, and return the number of files moved
+    ///
+    /// The files already in the archive folder or its subfolders are left in place
+    pub async fn archive_stale_files(
+        &self,
+        report: &RetentionReport,
+        archive: impl Into<UptoboxPath>,
+    ) -> UptoboxResult<usize> {
+        let archive = archive.into();
+        let codes: Vec<&str> = report
+            .files
+            .iter()
+            .filter(|m| !m.file.path.starts_with(&archive))
+            .map(|m| m.file.file.file_code.as_str())
+            .collect();
+        if codes.is_empty() {
+            return Ok(0);
+        }
+
+        let fld_id = self.create_folder_all(&archive).await?;
+        let mut moved = 0;
+        for batch in codes.chunks(BATCH_SIZE) {
+            moved += self.move_files(batch.iter().copied(), fld_id).await?;
+        }
+
+        Ok(moved)
+    }
+
+    /// Delete the stale files, and return the number of files deleted
+    pub async fn delete_stale_files(&self, report: &RetentionReport) -> UptoboxResult<usize> {
+        let mut deleted = 0;
+        for batch in report.files.chunks(BATCH_SIZE) {
+            deleted += self
+                .delete_files(batch.iter().map(|m| m.file.file.file_code.as_str()))
+                .await?;
+        }
+
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,164 @@
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use crate::{GetFiles, SearchAll, Uptobox, UptoboxResult, Walk, WalkFile, WalkFolder};
+
+/// Search
+impl Uptobox {
+    /// Search the files of a folder and of all its subfolders
+    ///
+    /// Each folder is searched by the api, and the folders are walked through recursively.
+    /// The search is lazy: it stops once the limit is reached or when the stream is dropped
+    pub fn search_all<'a>(
+        &'a self,
+        search_all: &SearchAll,
+    ) -> impl Stream<Item = UptoboxResult<WalkFile>> + 'a {
+        let (folders, search) = search_all.walks();
+
+        let results = self.walk(&folders).and_then(move |folder| {
+            let search = search.clone();
+            async move { self.walk_folder(&search, folder.path).await }
+        });
+
+        files(results, search_all.limit)
+    }
+}
+
+impl SearchAll {
+    /// The walk through the folders, which ignores the search, and the walk searching each folder
+    fn walks(&self) -> (Walk, Walk) {
+        let mut folders = Walk::new(&self.path);
+        folders.get_files(GetFiles::default().limit(1));
+        folders.first_page_only = true;
+        if let Some(max_depth) = self.max_depth {
+            folders.max_depth(max_depth);
+        }
+
+        let mut search = Walk::new(&self.path);
+        search.get_files(GetFiles::default().search(self.search_field, self.search.clone()));
+
+        (folders, search)
+    }
+}
+
+/// The files of the folders searched, the folders are no longer polled once the limit is reached
+fn files<'a>(
+    folders: impl Stream<Item = UptoboxResult<WalkFolder>> + 'a,
+    limit: Option<usize>,
+) -> impl Stream<Item = UptoboxResult<WalkFile>> + 'a {
+    folders
+        .map_ok(|folder| futures::stream::iter(folder.into_files().map(Ok)))
+        .try_flatten()
+        .take(limit.unwrap_or(usize::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SearchField, UptoboxPath};
+    use futures::executor::block_on;
+    use serde_json::{json, Value};
+
+    fn query(walk: &Walk) -> Value {
+        serde_json::to_value(&walk.get_files).unwrap()
+    }
+
+    fn folder(path: &str, codes: &[&str]) -> WalkFolder {
+        let files = codes
+            .iter()
+            .map(|code| {
+                serde_json::from_value(json!({
+                    "file_code": code,
+                    "file_descr": "",
+                    "file_downloads": 0,
+                    "file_name": "a.txt",
+                    "file_public": 0,
+                    "file_size": 10,
+                    "nb_stream": 0
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        WalkFolder {
+            path: UptoboxPath::parse(path),
+            current_folder: serde_json::from_value(json!({
+                "fileCount": codes.len(),
+                "fld_id": 1,
+                "hash": "hash",
+                "totalFileSize": 0
+            }))
+            .unwrap(),
+            folders: Vec::new(),
+            files,
+        }
+    }
+
+    #[test]
+    fn walks() {
+        let mut search_all = SearchAll::new("movie");
+        search_all
+            .path("//dev")
+            .search_field(SearchField::FileDescr)
+            .max_depth(2)
+            .limit(10);
+        let (folders, search) = search_all.walks();
+
+        // The folders are walked through with the first page of one file, without the search
+        assert_eq!(
+            query(&folders),
+            json!({ "path": "//dev", "limit": 1, "orderBy": "file_name", "dir": "ASC", "offset": 0 })
+        );
+        assert!(folders.first_page_only);
+        assert_eq!(folders.max_depth, Some(2));
+
+        assert_eq!(
+            query(&search),
+            json!({
+                "path": "//dev",
+                "limit": 100,
+                "orderBy": "file_name",
+                "dir": "ASC",
+                "offset": 0,
+                "searchField": "file_descr",
+                "search": "movie"
+            })
+        );
+        assert!(!search.first_page_only);
+        assert_eq!(search.max_depth, None);
+
+        let (folders, _) = SearchAll::new("movie").walks();
+        assert_eq!(folders.max_depth, None);
+        assert_eq!(query(&folders)["path"], "///");
+    }
+
+    #[test]
+    fn limit_stops_the_search() {
+        let folders = futures::stream::iter([
+            Ok(folder("//a", &["aaaaaaaaaaa1", "aaaaaaaaaaa2"])),
+            Ok(folder("//b", &["bbbbbbbbbbb1"])),
+        ])
+        // The folders after the limit must not be searched
+        .chain(futures::stream::once(async {
+            panic!("searched a folder after the limit")
+        }));
+
+        let files: Vec<WalkFile> = block_on(files(folders, Some(3)).try_collect()).unwrap();
+        let codes: Vec<&str> = files.iter().map(|f| f.file.file_code.as_str()).collect();
+
+        assert_eq!(codes, ["aaaaaaaaaaa1", "aaaaaaaaaaa2", "bbbbbbbbbbb1"]);
+        assert_eq!(files[2].path, UptoboxPath::parse("//b"));
+    }
+
+    #[test]
+    fn without_limit() {
+        let folders = futures::stream::iter([
+            Ok(folder("//a", &["aaaaaaaaaaa1"])),
+            Ok(folder("//b", &[])),
+            Ok(folder("//c", &["ccccccccccc1"])),
+        ]);
+
+        let files: Vec<WalkFile> = block_on(files(folders, None).try_collect()).unwrap();
+
+        assert_eq!(files.len(), 2);
+    }
+}
@@ -1,40 +1,233 @@
-use crate::{Error, UptoboxResult};
+use std::borrow::Cow;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::model::de::with_lenient;
+use crate::{ApiResponse, Error, HttpErrorResponse, UptoboxResult};
+
+/// Placeholder for the secrets (token, passwords) in the debug outputs
+pub const REDACTED: &str = "[redacted]";
+
+/// Number of files sent by each bulk request
+pub(crate) const BATCH_SIZE: usize = 100;
 
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorDeserialize {
     pub status_code: usize,
     pub message: String,
-    pub data: Option<String>,
+    pub data: Option<Value>,
+}
+
+/// Body of a response along with its metadata
+pub struct HttpResponse {
+    pub body: String,
+    pub headers: HeaderMap,
+    pub elapsed: Duration,
+    /// Deserialize the optional fields of an unexpected type as `None`
+    pub lenient: bool,
+    #[cfg(feature = "tracing")]
+    pub span: tracing::Span,
 }
 
-pub fn deserialize<'de, T>(json: &'de str) -> UptoboxResult<T>
+pub fn deserialize<'de, T>(response: &'de HttpResponse) -> UptoboxResult<ApiResponse<T::Data>>
 where
     T: serde::Deserialize<'de> + DeserializeCheck,
 {
-    match serde_json::from_str::<T>(json) {
+    let json = response.body.as_str();
+
+    let (parsed, warnings) = with_lenient(response.lenient, || serde_json::from_str::<T>(json));
+
+    let result = match parsed {
         Ok(r) => match r.status_code() {
-            0 | 16 | 39 => Ok(r),
-            _ => match serde_json::from_str::<ErrorDeserialize>(json) {
-                Ok(r) => Err(Error::ParseResponse(
-                    r.status_code,
-                    r.message,
-                    r.data.unwrap_or_default(),
-                )),
-                Err(e) => Err(Error::UnknownParseResponse(e)),
-            },
-        },
-        Err(e) => match serde_json::from_str::<ErrorDeserialize>(json) {
-            Ok(r) => Err(Error::ParseResponse(
-                r.status_code,
-                r.message,
-                r.data.unwrap_or_default(),
-            )),
-            Err(_) => Err(Error::UnknownParseResponse(e)),
+            0 | 16 | 39 => {
+                let status_code = r.status_code();
+                let (message, data) = r.into_parts();
+
+                Ok(ApiResponse {
+                    data,
+                    status_code,
+                    message,
+                    headers: response.headers.clone(),
+                    elapsed: response.elapsed,
+                    warnings,
+                })
+            }
+            _ => Err(deserialize_error(json).unwrap_or_else(Error::UnknownParseResponse)),
         },
+        Err(e) => Err(deserialize_error(json).unwrap_or(Error::UnknownParseResponse(e))),
+    };
+
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(r) => {
+            response.span.record("api_status_code", r.status_code);
+            tracing::debug!(parent: &response.span, "response parsed");
+        }
+        Err(Error::ParseResponse(status_code, ..)) => {
+            response.span.record("api_status_code", status_code);
+            tracing::warn!(parent: &response.span, "api returned an error");
+        }
+        Err(_) => tracing::warn!(parent: &response.span, "unable to parse the response"),
+    }
+
+    result
+}
+
+/// Parse the error returned by the api
+pub fn deserialize_error(json: &str) -> Result<Error, serde_json::Error> {
+    serde_json::from_str::<ErrorDeserialize>(json).map(|r| {
+        let data = match r.data {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(data)) => data,
+            Some(data) => data.to_string(),
+        };
+
+        Error::ParseResponse(r.status_code, r.message, data)
+    })
+}
+
+/// Build the error of a response with a status code other than 200
+///
+/// The error of the api is kept along with the raw response if the body holds one
+pub fn http_error(response: HttpErrorResponse) -> Error {
+    match deserialize_error(&response.body) {
+        Ok(error) => Error::HttpResponseApiError(Box::new(response), Box::new(error)),
+        Err(_) => Error::HttpResponseCode(Box::new(response)),
+    }
+}
+
+/// Escape a field of a csv file
+pub fn csv_field(field: &str) -> Cow<'_, str> {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => Cow::Owned(format!("\"{}\"", field.replace('"', "\"\""))),
+        false => Cow::Borrowed(field),
     }
 }
 
 pub trait DeserializeCheck {
+    /// The data returned to the user
+    type Data;
+
     fn status_code(&self) -> usize;
+
+    /// Split the wrapper into its message and its data
+    fn into_parts(self) -> (Option<String>, Self::Data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::get_download_url::GetDownloadUrlResponseWrapper;
+    use crate::{GetDownloadUrlResponse, RawResponse};
+
+    fn http_response(body: &str) -> HttpResponse {
+        HttpResponse {
+            body: body.into(),
+            headers: HeaderMap::new(),
+            elapsed: Duration::from_millis(10),
+            lenient: false,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
+        }
+    }
+
+    fn response(status: u16, body: &str) -> HttpErrorResponse {
+        HttpErrorResponse {
+            status,
+            path: "/api/link".into(),
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    #[test]
+    fn http_error_with_api_body() {
+        let error = http_error(response(
+            503,
+            r#"{"statusCode":39,"message":"Need to wait","data":{"waiting":30}}"#,
+        ));
+
+        assert!(matches!(error, Error::HttpResponseApiError(..)));
+        assert_eq!(error.http_status(), Some(503));
+        assert_eq!(error.api_status(), Some(39));
+        assert_eq!(error.wait_seconds(), Some(30));
+    }
+
+    #[test]
+    fn http_error_with_raw_body() {
+        let error = http_error(response(502, "<html>Bad Gateway</html>"));
+
+        assert!(matches!(error, Error::HttpResponseCode(..)));
+        assert_eq!(error.http_status(), Some(502));
+        assert_eq!(error.api_status(), None);
+    }
+
+    #[test]
+    fn raw_response() {
+        let response = http_response(r#"{"statusCode":0,"message":"Success"}"#);
+        let raw = RawResponse::from(deserialize::<RawResponse>(&response).unwrap());
+
+        assert_eq!(
+            raw,
+            RawResponse {
+                status_code: 0,
+                data: Value::Null,
+                message: Some("Success".into()),
+            }
+        );
+
+        let response = http_response(r#"{"statusCode":0,"data":{"a":1}}"#);
+        let raw = RawResponse::from(deserialize::<RawResponse>(&response).unwrap());
+        assert_eq!(raw.data, serde_json::json!({ "a": 1 }));
+        assert_eq!(raw.message, None);
+    }
+
+    #[test]
+    fn deserialize_success() {
+        let response = http_response(
+            r#"{"statusCode":0,"message":"Success","data":{"dlLink":"https://www1.uptobox.com/dl/abc"}}"#,
+        );
+        let result = deserialize::<GetDownloadUrlResponseWrapper>(&response).unwrap();
+
+        assert_eq!(result.status_code, 0);
+        assert_eq!(result.message.as_deref(), Some("Success"));
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.elapsed, Duration::from_millis(10));
+        assert!(matches!(result.data, GetDownloadUrlResponse::Link(_)));
+    }
+
+    #[test]
+    fn deserialize_waiting() {
+        for status_code in [16, 39] {
+            let mut response = http_response(&format!(
+                r#"{{"statusCode":{status_code},"message":"Waiting needed","data":{{"waiting":30,"waitingToken":12}}}}"#
+            ));
+            response.lenient = true;
+            let result = deserialize::<GetDownloadUrlResponseWrapper>(&response).unwrap();
+
+            assert_eq!(result.status_code, status_code);
+            assert_eq!(result.message.as_deref(), Some("Waiting needed"));
+            assert_eq!(result.warnings.len(), 1);
+            match result.data {
+                GetDownloadUrlResponse::Wait(wait) => {
+                    assert_eq!(wait.waiting, 30);
+                    assert_eq!(wait.waiting_token, None);
+                }
+                other => panic!("unexpected data: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize_api_error() {
+        let response = http_response(r#"{"statusCode":28,"message":"File not found","data":null}"#);
+
+        match deserialize::<GetDownloadUrlResponseWrapper>(&response) {
+            Err(Error::ParseResponse(28, message, _)) => assert_eq!(message, "File not found"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
 }
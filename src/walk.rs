@@ -0,0 +1,111 @@
+use futures::{Stream, TryStreamExt};
+use std::collections::VecDeque;
+
+use crate::{
+    GetFilesCurrentFolder, GetFilesFiles, GetFilesFolders, Uptobox, UptoboxPath, UptoboxResult,
+    Walk,
+};
+
+/// Folder visited by a walk, with the files of all its pages
+#[derive(Debug, Clone)]
+pub struct WalkFolder {
+    pub path: UptoboxPath,
+    pub current_folder: GetFilesCurrentFolder,
+    pub folders: Vec<GetFilesFolders>,
+    pub files: Vec<GetFilesFiles>,
+}
+
+impl WalkFolder {
+    /// The files of the folder, with the path of the folder
+    pub fn into_files(self) -> impl Iterator<Item = WalkFile> {
+        let path = self.path;
+        self.files.into_iter().map(move |file| WalkFile {
+            path: path.clone(),
+            file,
+        })
+    }
+}
+
+/// File visited by a walk
+#[derive(Debug, Clone)]
+pub struct WalkFile {
+    /// The path of the folder of the file
+    pub path: UptoboxPath,
+    pub file: GetFilesFiles,
+}
+
+/// Walk
+impl Uptobox {
+    /// Walk through a folder and its subfolders
+    ///
+    /// The folders are visited breadth first, and lazily: dropping the stream stops the walk
+    pub fn walk<'a>(&'a self, walk: &Walk) -> impl Stream<Item = UptoboxResult<WalkFolder>> + 'a {
+        let walk = walk.clone();
+        let root = walk.get_files.path_ref().clone();
+        let queue = VecDeque::from([root.clone()]);
+
+        futures::stream::try_unfold(queue, move |mut queue| {
+            let walk = walk.clone();
+            let root = root.clone();
+
+            async move {
+                let path = match queue.pop_front() {
+                    Some(path) => path,
+                    None => return Ok(None),
+                };
+
+                let folder = self.walk_folder(&walk, path).await?;
+                let depth = folder.path.depth().saturating_sub(root.depth());
+                if walk.max_depth.is_none_or(|max| depth < max) {
+                    queue.extend(folder.folders.iter().map(|f| f.path(&folder.path)));
+                }
+
+                Ok(Some((folder, queue)))
+            }
+        })
+    }
+
+    /// Walk through a folder and its subfolders, and return their files
+    pub fn walk_files<'a>(
+        &'a self,
+        walk: &Walk,
+    ) -> impl Stream<Item = UptoboxResult<WalkFile>> + 'a {
+        self.walk(walk)
+            .map_ok(|folder| futures::stream::iter(folder.into_files().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Retrieve all the pages of a folder
+    pub(crate) async fn walk_folder(
+        &self,
+        walk: &Walk,
+        path: UptoboxPath,
+    ) -> UptoboxResult<WalkFolder> {
+        let mut get_files = walk.get_files.clone();
+        get_files.path(&path).offset(0);
+
+        let response = self.get_files(&get_files).await?;
+        let mut folder = WalkFolder {
+            path,
+            current_folder: response.current_folder,
+            folders: response.folders,
+            files: response.files,
+        };
+
+        if walk.first_page_only {
+            return Ok(folder);
+        }
+
+        for page in 1..response.page_count {
+            get_files.offset(page * get_files.limit_value());
+            let response = self.get_files(&get_files).await?;
+            if response.files.is_empty() {
+                break;
+            }
+
+            folder.files.extend(response.files);
+        }
+
+        Ok(folder)
+    }
+}
@@ -0,0 +1,92 @@
+use rs_uptobox::{FileStats, StatsSnapshot, UptoboxPath};
+
+fn stats(code: &str, downloads: usize, streams: usize) -> FileStats {
+    FileStats {
+        path: UptoboxPath::parse("//dev"),
+        file_code: code.into(),
+        file_name: format!("{code}.txt"),
+        file_size: 10,
+        downloads,
+        streams,
+        last_download: String::new(),
+        last_stream: String::new(),
+    }
+}
+
+fn snapshot(taken_at: u64, files: Vec<FileStats>) -> StatsSnapshot {
+    StatsSnapshot { taken_at, files }
+}
+
+#[test]
+fn trends() {
+    let older = snapshot(
+        1000,
+        vec![
+            stats("aaaaaaaaaaaa", 10, 1),
+            stats("bbbbbbbbbbbb", 5, 5),
+            stats("removed00000", 100, 0),
+        ],
+    );
+    let newer = snapshot(
+        4600,
+        vec![
+            stats("aaaaaaaaaaaa", 12, 4),
+            stats("bbbbbbbbbbbb", 25, 5),
+            stats("added0000000", 3, 2),
+        ],
+    );
+    let report = older.trends(&newer);
+
+    assert_eq!(report.elapsed, 3600);
+
+    // The files of the newer snapshot only, from the most downloaded since the older snapshot
+    let trends: Vec<(&str, usize, i64, usize, i64)> = report
+        .files
+        .iter()
+        .map(|f| {
+            (
+                f.file_code.as_str(),
+                f.downloads,
+                f.downloads_delta,
+                f.streams,
+                f.streams_delta,
+            )
+        })
+        .collect();
+    assert_eq!(
+        trends,
+        [
+            ("bbbbbbbbbbbb", 25, 20, 5, 0),
+            ("added0000000", 3, 3, 2, 2),
+            ("aaaaaaaaaaaa", 12, 2, 4, 3),
+        ]
+    );
+}
+
+#[test]
+fn negative_deltas() {
+    let older = snapshot(1000, vec![stats("aaaaaaaaaaaa", 10, 3)]);
+    let newer = snapshot(2000, vec![stats("aaaaaaaaaaaa", 4, 0)]);
+    let report = older.trends(&newer);
+
+    assert_eq!(report.files[0].downloads_delta, -6);
+    assert_eq!(report.files[0].streams_delta, -3);
+}
+
+#[test]
+fn snapshots_in_the_wrong_order() {
+    let older = snapshot(2000, vec![stats("aaaaaaaaaaaa", 1, 0)]);
+    let newer = snapshot(1000, vec![]);
+    let report = older.trends(&newer);
+
+    assert_eq!(report.elapsed, 0);
+    assert!(report.files.is_empty());
+}
+
+#[test]
+fn json_round_trip() {
+    let snapshot = snapshot(1000, vec![stats("aaaaaaaaaaaa", 1, 2)]);
+    let json = snapshot.to_json().unwrap();
+
+    assert_eq!(StatsSnapshot::from_json(&json).unwrap(), snapshot);
+}
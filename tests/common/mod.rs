@@ -0,0 +1,104 @@
+//! Helpers shared by the tests
+#![allow(dead_code)]
+
+use serde_json::json;
+
+use rs_uptobox::{FileVisibility, GetFilesFiles, ManifestFile, UptoboxPath, WalkFile};
+
+/// Builder of the files of the listings, private and never downloaded by default
+pub struct FileBuilder {
+    file: GetFilesFiles,
+}
+
+/// Start building a file
+pub fn file(code: &str) -> FileBuilder {
+    let file = serde_json::from_value(json!({
+        "file_code": code,
+        "file_created": "",
+        "file_descr": "",
+        "file_downloads": 0,
+        "file_last_download": "",
+        "file_name": "a.txt",
+        "file_password": "",
+        "file_public": 0,
+        "file_size": 10,
+        "last_stream": "",
+        "nb_stream": 0
+    }))
+    .unwrap();
+
+    FileBuilder { file }
+}
+
+impl FileBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.file.file_name = name.into();
+        self
+    }
+
+    pub fn size(mut self, size: usize) -> Self {
+        self.file.file_size = size;
+        self
+    }
+
+    pub fn downloads(mut self, downloads: usize) -> Self {
+        self.file.file_downloads = downloads;
+        self
+    }
+
+    pub fn created(mut self, date: &str) -> Self {
+        self.file.file_created = date.into();
+        self
+    }
+
+    pub fn last_download(mut self, date: &str) -> Self {
+        self.file.file_last_download = date.into();
+        self
+    }
+
+    pub fn last_stream(mut self, date: &str) -> Self {
+        self.file.last_stream = date.into();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.file.file_descr = description.into();
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.file.file_password = Some(password.into());
+        self
+    }
+
+    pub fn public(mut self) -> Self {
+        self.file.file_public = FileVisibility::Public;
+        self
+    }
+
+    pub fn build(self) -> GetFilesFiles {
+        self.file
+    }
+
+    /// The file in a folder of a walk
+    pub fn walk_file(self, path: &str) -> WalkFile {
+        WalkFile {
+            path: UptoboxPath::parse(path),
+            file: self.file,
+        }
+    }
+
+    /// The file in a folder of a manifest
+    pub fn manifest_file(self, path: &str) -> ManifestFile {
+        ManifestFile {
+            path: UptoboxPath::parse(path),
+            file: self.file,
+        }
+    }
+}
+
+/// Parse a date in the format of the api
+#[cfg(feature = "chrono")]
+pub fn date(date: &str) -> chrono::NaiveDateTime {
+    chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use serde_json::json;
+
+use common::file;
+use rs_uptobox::{DiskUsage, DiskUsageReport, GetFilesFiles, UptoboxPath, WalkFolder};
+
+fn folder(path: &str, sizes: &[usize]) -> WalkFolder {
+    let files: Vec<GetFilesFiles> = sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &size)| {
+            file(&format!("{path}-{i}"))
+                .name(&format!("file{i}"))
+                .size(size)
+                .build()
+        })
+        .collect();
+
+    WalkFolder {
+        path: UptoboxPath::parse(path),
+        current_folder: serde_json::from_value(json!({
+            "fileCount": files.len(),
+            "fld_id": 1,
+            "hash": "hash",
+            "totalFileSize": sizes.iter().sum::<usize>()
+        }))
+        .unwrap(),
+        folders: Vec::new(),
+        files,
+    }
+}
+
+/// Path, own size, total size and total file count of a folder and its subfolders, depth first
+fn flatten(usage: &DiskUsage) -> Vec<(String, usize, usize, usize)> {
+    let mut flat = vec![(
+        usage.path.to_string(),
+        usage.own_size,
+        usage.total_size,
+        usage.total_file_count,
+    )];
+    for child in &usage.children {
+        flat.extend(flatten(child));
+    }
+    flat
+}
+
+#[test]
+fn totals_rolled_up() {
+    let folders = [
+        folder("//dev", &[10]),
+        folder("//dev/docs", &[1, 2]),
+        folder("//dev/builds", &[100]),
+        folder("//dev/builds/old", &[200, 300]),
+        folder("//dev/builds/empty", &[]),
+    ];
+    let report = DiskUsageReport::from_folders(&folders, 2);
+
+    // The subfolders are sorted from the largest
+    assert_eq!(
+        flatten(&report.root),
+        [
+            ("//dev".into(), 10, 613, 6),
+            ("//dev/builds".into(), 100, 600, 3),
+            ("//dev/builds/old".into(), 500, 500, 2),
+            ("//dev/builds/empty".into(), 0, 0, 0),
+            ("//dev/docs".into(), 3, 3, 2),
+        ]
+    );
+    assert_eq!(report.root.own_file_count, 1);
+
+    let largest: Vec<(String, usize)> = report
+        .largest_files
+        .iter()
+        .map(|f| (f.path.to_string(), f.file_size))
+        .collect();
+    assert_eq!(
+        largest,
+        [
+            ("//dev/builds/old".into(), 300),
+            ("//dev/builds/old".into(), 200)
+        ]
+    );
+}
+
+#[test]
+fn root_folder() {
+    let folders = [
+        folder("//", &[1]),
+        folder("//a", &[2]),
+        folder("//a/b", &[4]),
+        folder("//c", &[8]),
+    ];
+    let report = DiskUsageReport::from_folders(&folders, 0);
+
+    assert_eq!(
+        flatten(&report.root),
+        [
+            ("//".into(), 1, 15, 4),
+            ("//c".into(), 8, 8, 1),
+            ("//a".into(), 2, 6, 2),
+            ("//a/b".into(), 4, 4, 1),
+        ]
+    );
+    assert!(report.largest_files.is_empty());
+}
+
+#[test]
+fn empty_walk() {
+    let report = DiskUsageReport::from_folders(&[], 10);
+
+    assert!(report.root.path.is_root());
+    assert_eq!(report.root.total_size, 0);
+    assert!(report.root.children.is_empty());
+}
+
+#[test]
+fn tree() {
+    let folders = [folder("//dev", &[2048]), folder("//dev/docs", &[10])];
+    let report = DiskUsageReport::from_folders(&folders, 0);
+
+    assert_eq!(
+        report.to_string(),
+        "//dev 2.0 KiB (2 files)\n └── docs 10 B (1 files)\n"
+    );
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::file;
+use rs_uptobox::{DuplicateReport, FileCode, FindDuplicates, KeepStrategy, WalkFile};
+
+fn codes(files: &[WalkFile]) -> Vec<&str> {
+    files.iter().map(|f| f.file.file_code.as_str()).collect()
+}
+
+fn report(files: Vec<WalkFile>) -> DuplicateReport {
+    DuplicateReport::from_files(files, &FindDuplicates::new())
+}
+
+#[test]
+fn groups() {
+    let report = report(vec![
+        file("aaaaaaaaaaa1").walk_file("//dev"),
+        file("aaaaaaaaaaa2").walk_file("//dev"),
+        file("bbbbbbbbbbb1")
+            .name("b.txt")
+            .size(100)
+            .walk_file("//dev"),
+        file("bbbbbbbbbbb2")
+            .name("b.txt")
+            .size(100)
+            .walk_file("//dev"),
+        file("bbbbbbbbbbb3")
+            .name("b.txt")
+            .size(100)
+            .walk_file("//dev"),
+        // Same name, another size
+        file("aaaaaaaaaaa3").size(11).walk_file("//dev"),
+        file("cccccccccccc").name("c.txt").walk_file("//dev"),
+    ]);
+
+    // From the group wasting the most space
+    let groups: Vec<(&str, usize, Vec<&str>)> = report
+        .groups
+        .iter()
+        .map(|g| (g.file_name.as_str(), g.wasted_space(), codes(&g.files)))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (
+                "b.txt",
+                200,
+                vec!["bbbbbbbbbbb1", "bbbbbbbbbbb2", "bbbbbbbbbbb3"]
+            ),
+            ("a.txt", 10, vec!["aaaaaaaaaaa1", "aaaaaaaaaaa2"]),
+        ]
+    );
+    assert_eq!(report.wasted_space(), 210);
+}
+
+#[test]
+fn content_hashes() {
+    let mut find_duplicates = FindDuplicates::new();
+    find_duplicates
+        .content_hash(&FileCode::parse("aaaaaaaaaaa1").unwrap(), "hash1")
+        .content_hashes([("aaaaaaaaaaa2".to_string(), "hash1".to_string())])
+        .content_hash(&FileCode::parse("aaaaaaaaaaa3").unwrap(), "hash2");
+    let report = DuplicateReport::from_files(
+        vec![
+            file("aaaaaaaaaaa1").walk_file("//dev"),
+            file("aaaaaaaaaaa2").walk_file("//dev"),
+            file("aaaaaaaaaaa3").walk_file("//dev"),
+            file("aaaaaaaaaaa4").walk_file("//dev"),
+        ],
+        &find_duplicates,
+    );
+
+    assert_eq!(report.groups.len(), 1);
+    assert_eq!(report.groups[0].content_hash.as_deref(), Some("hash1"));
+    assert_eq!(
+        codes(&report.groups[0].files),
+        ["aaaaaaaaaaa1", "aaaaaaaaaaa2"]
+    );
+}
+
+#[test]
+fn keep_strategies() {
+    let report = report(vec![
+        file("aaaaaaaaaaa1")
+            .created("2023-02-01 00:00:00")
+            .downloads(1)
+            .walk_file("//dev"),
+        file("aaaaaaaaaaa2")
+            .created("2023-01-01 00:00:00")
+            .downloads(5)
+            .walk_file("//dev"),
+        file("aaaaaaaaaaa3")
+            .created("2023-03-01 00:00:00")
+            .downloads(2)
+            .walk_file("//dev"),
+    ]);
+
+    for (strategy, kept) in [
+        (KeepStrategy::Oldest, "aaaaaaaaaaa2"),
+        (KeepStrategy::Newest, "aaaaaaaaaaa3"),
+        (KeepStrategy::MostDownloaded, "aaaaaaaaaaa2"),
+    ] {
+        let plan = report.plan(strategy);
+        assert_eq!(codes(&plan.keep), [kept], "{strategy:?}");
+        assert_eq!(plan.delete.len(), 2);
+        assert!(!codes(&plan.delete).contains(&kept));
+        assert_eq!(plan.freed_space(), 20);
+    }
+}
+
+#[test]
+fn files_without_creation_date_are_not_preferred() {
+    let report = report(vec![
+        file("aaaaaaaaaaa1").walk_file("//dev"),
+        file("aaaaaaaaaaa2")
+            .created("0000-00-00 00:00:00")
+            .walk_file("//dev"),
+        file("aaaaaaaaaaa3")
+            .created("2023-02-01 00:00:00")
+            .walk_file("//dev"),
+        file("aaaaaaaaaaa4")
+            .created("2023-01-01 00:00:00")
+            .walk_file("//dev"),
+    ]);
+
+    assert_eq!(
+        codes(&report.plan(KeepStrategy::Oldest).keep),
+        ["aaaaaaaaaaa4"]
+    );
+    assert_eq!(
+        codes(&report.plan(KeepStrategy::Newest).keep),
+        ["aaaaaaaaaaa3"]
+    );
+
+    // Without any date, the ties are broken by file code
+    let report = self::report(vec![
+        file("aaaaaaaaaaa2").walk_file("//dev"),
+        file("aaaaaaaaaaa1")
+            .created("0000-00-00 00:00:00")
+            .walk_file("//dev"),
+    ]);
+    assert_eq!(
+        codes(&report.plan(KeepStrategy::Oldest).keep),
+        ["aaaaaaaaaaa1"]
+    );
+    assert_eq!(
+        codes(&report.plan(KeepStrategy::Newest).keep),
+        ["aaaaaaaaaaa1"]
+    );
+}
+
+#[test]
+fn ties_broken_by_file_code() {
+    let report = report(vec![
+        file("aaaaaaaaaaa2")
+            .created("2023-01-01 00:00:00")
+            .downloads(3)
+            .walk_file("//dev"),
+        file("aaaaaaaaaaa1")
+            .created("2023-01-01 00:00:00")
+            .downloads(3)
+            .walk_file("//dev"),
+    ]);
+
+    for strategy in [
+        KeepStrategy::Oldest,
+        KeepStrategy::Newest,
+        KeepStrategy::MostDownloaded,
+    ] {
+        assert_eq!(codes(&report.plan(strategy).keep), ["aaaaaaaaaaa1"]);
+    }
+}
@@ -0,0 +1,131 @@
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use rs_uptobox::{Error, HttpErrorResponse};
+
+fn api_error(status_code: usize, data: &str) -> Error {
+    Error::ParseResponse(status_code, "message".into(), data.into())
+}
+
+fn http_error(status: u16) -> Error {
+    http_error_with_headers(status, HeaderMap::new())
+}
+
+fn http_api_error(status: u16, headers: HeaderMap, error: Error) -> Error {
+    match http_error_with_headers(status, headers) {
+        Error::HttpResponseCode(response) => Error::HttpResponseApiError(response, Box::new(error)),
+        _ => unreachable!(),
+    }
+}
+
+fn http_error_with_headers(status: u16, headers: HeaderMap) -> Error {
+    Error::HttpResponseCode(Box::new(HttpErrorResponse {
+        status,
+        path: "/api/user/me".into(),
+        headers,
+        body: "<html></html>".into(),
+    }))
+}
+
+#[test]
+fn auth_failure() {
+    assert!(api_error(2, "").is_auth_failure());
+    assert!(api_error(13, "").is_auth_failure());
+    assert!(http_error(401).is_auth_failure());
+    assert!(http_error(403).is_auth_failure());
+
+    assert!(!api_error(7, "bad token").is_auth_failure());
+    assert!(!api_error(28, "").is_auth_failure());
+    assert!(!http_error(404).is_auth_failure());
+}
+
+#[test]
+fn not_found() {
+    assert!(api_error(28, "").is_not_found());
+    assert!(http_error(404).is_not_found());
+
+    assert!(!api_error(2, "").is_not_found());
+    assert!(!http_error(500).is_not_found());
+}
+
+#[test]
+fn premium_required() {
+    assert!(api_error(6, "").is_premium_required());
+
+    assert!(!api_error(2, "").is_premium_required());
+    assert!(!http_error(403).is_premium_required());
+}
+
+#[test]
+fn rate_limited() {
+    assert!(api_error(16, "").is_rate_limited());
+    assert!(api_error(39, "").is_rate_limited());
+    assert!(http_error(429).is_rate_limited());
+
+    assert!(!api_error(1, "").is_rate_limited());
+    assert!(!http_error(503).is_rate_limited());
+}
+
+#[test]
+fn retryable() {
+    assert!(api_error(16, "").is_retryable());
+    assert!(http_error(429).is_retryable());
+    assert!(http_error(500).is_retryable());
+    assert!(http_error(502).is_retryable());
+    assert!(http_error(503).is_retryable());
+    assert!(http_error(504).is_retryable());
+
+    assert!(!api_error(2, "").is_retryable());
+    assert!(!api_error(28, "").is_retryable());
+    assert!(!http_error(400).is_retryable());
+    assert!(!http_error(404).is_retryable());
+}
+
+#[test]
+fn wait_seconds() {
+    assert_eq!(api_error(39, r#"{"waiting":30}"#).wait_seconds(), Some(30));
+    assert_eq!(api_error(16, "45").wait_seconds(), Some(45));
+    assert_eq!(api_error(16, "").wait_seconds(), None);
+    assert_eq!(api_error(1, "bad token").wait_seconds(), None);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+    assert_eq!(
+        http_error_with_headers(429, headers).wait_seconds(),
+        Some(120)
+    );
+    assert_eq!(http_error(429).wait_seconds(), None);
+}
+
+#[test]
+fn status_codes() {
+    assert_eq!(api_error(28, "").api_status(), Some(28));
+    assert_eq!(api_error(28, "").http_status(), None);
+    assert_eq!(http_error(404).http_status(), Some(404));
+    assert_eq!(http_error(404).api_status(), None);
+}
+
+#[test]
+fn http_api_error_keeps_both_status_codes() {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("60"));
+
+    let error = http_api_error(429, headers.clone(), api_error(1, ""));
+    assert_eq!(error.http_status(), Some(429));
+    assert_eq!(error.api_status(), Some(1));
+    assert!(error.is_rate_limited());
+    assert!(error.is_retryable());
+    assert_eq!(error.wait_seconds(), Some(60));
+
+    let error = http_api_error(503, HeaderMap::new(), api_error(1, ""));
+    assert!(error.is_retryable());
+
+    // The waiting time of the api takes precedence over the header
+    let error = http_api_error(429, headers, api_error(39, r#"{"waiting":30}"#));
+    assert_eq!(error.wait_seconds(), Some(30));
+
+    let error = http_api_error(400, HeaderMap::new(), api_error(13, ""));
+    assert!(error.is_auth_failure());
+    assert!(!error.is_retryable());
+
+    let error = http_api_error(404, HeaderMap::new(), api_error(28, ""));
+    assert!(error.is_not_found());
+}
@@ -0,0 +1,120 @@
+use rs_uptobox::{extract_links, FileCode, PublicFolder};
+
+fn codes(text: &str) -> Vec<String> {
+    extract_links(text)
+        .files
+        .iter()
+        .map(|f| f.code().to_string())
+        .collect()
+}
+
+#[test]
+fn plain_text() {
+    let text = "First https://uptobox.com/aaaaaaaaaaaa then\nuptostream.com/iframe/bbbbbbbbbbbb\tand www.uptobox.fr/cccccccccccc";
+
+    assert_eq!(
+        codes(text),
+        ["aaaaaaaaaaaa", "bbbbbbbbbbbb", "cccccccccccc"]
+    );
+}
+
+#[test]
+fn uppercase_hosts() {
+    let text = "HTTPS://UPTOBOX.COM/abcdef123456 and https://Uptobox.com/bbbbbbbbbbbb";
+
+    assert_eq!(codes(text), ["abcdef123456", "bbbbbbbbbbbb"]);
+}
+
+#[test]
+fn other_domains() {
+    let text = "https://notuptobox.com/aaaaaaaaaaaa https://uptobox.community/bbbbbbbbbbbb";
+
+    assert!(codes(text).is_empty());
+}
+
+#[test]
+fn html() {
+    let html = r#"<a href="https://uptobox.com/aaaaaaaaaaaa">link</a>
+        <a href='https://uptobox.com/user_public?hash=abcdef&amp;folder=123'>folder</a>
+        <iframe src="https://uptostream.com/iframe/bbbbbbbbbbbb"></iframe>"#;
+    let links = extract_links(html);
+
+    assert_eq!(codes(html), ["aaaaaaaaaaaa", "bbbbbbbbbbbb"]);
+    assert_eq!(
+        links.public_folders,
+        [PublicFolder {
+            folder: 123,
+            hash: "abcdef".into()
+        }]
+    );
+}
+
+#[test]
+fn trailing_punctuation() {
+    let text = "See https://uptobox.com/aaaaaaaaaaaa. Or https://uptobox.com/bbbbbbbbbbbb, \
+        (https://uptobox.com/cccccccccccc) or https://uptobox.com/dddddddddddd?!";
+
+    assert_eq!(
+        codes(text),
+        [
+            "aaaaaaaaaaaa",
+            "bbbbbbbbbbbb",
+            "cccccccccccc",
+            "dddddddddddd"
+        ]
+    );
+}
+
+#[test]
+fn passwords() {
+    let links = extract_links(
+        "https://uptobox.com/aaaaaaaaaaaa:secret and https://uptobox.com/bbbbbbbbbbbb&#58;other",
+    );
+
+    assert_eq!(
+        links.files,
+        [
+            FileCode::parse("aaaaaaaaaaaa:secret").unwrap(),
+            FileCode::parse("bbbbbbbbbbbb:other").unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn public_folders() {
+    let links = extract_links(
+        "https://uptobox.com/user_public?folder=1&hash=aaa https://uptobox.com/user_public?hash=bbb&folder=2#top \
+        https://uptobox.com/user_public?folder=3",
+    );
+
+    assert_eq!(
+        links.public_folders,
+        [
+            PublicFolder {
+                folder: 1,
+                hash: "aaa".into()
+            },
+            PublicFolder {
+                folder: 2,
+                hash: "bbb".into()
+            },
+        ]
+    );
+    assert!(links.files.is_empty());
+}
+
+#[test]
+fn deduplication() {
+    let links = extract_links(
+        "https://uptobox.com/aaaaaaaaaaaa https://uptostream.com/aaaaaaaaaaaa \
+        https://uptobox.com/aaaaaaaaaaaa:secret \
+        https://uptobox.com/user_public?folder=1&hash=aaa https://uptobox.com/user_public?folder=1&hash=aaa",
+    );
+
+    // The password found later is attached to the known file
+    assert_eq!(
+        links.files,
+        [FileCode::parse("aaaaaaaaaaaa:secret").unwrap()]
+    );
+    assert_eq!(links.public_folders.len(), 1);
+}
@@ -0,0 +1,169 @@
+use serde_json::json;
+
+use rs_uptobox::{Error, FileCode, GetDownloadUrl, IntoFileCode, UpdateFile};
+
+fn parse(input: &str) -> (String, Option<String>) {
+    let file_code = FileCode::parse(input).unwrap();
+    (
+        file_code.code().to_string(),
+        file_code.password().map(String::from),
+    )
+}
+
+fn invalid(input: &str) -> String {
+    match FileCode::parse(input) {
+        Err(Error::InvalidFileCode(code)) => code,
+        other => panic!("{input} should be invalid, got {other:?}"),
+    }
+}
+
+#[test]
+fn bare_code() {
+    assert_eq!(parse("abcdef123456"), ("abcdef123456".into(), None));
+    assert_eq!(parse("  abcdef123456\n"), ("abcdef123456".into(), None));
+}
+
+#[test]
+fn uptobox_url() {
+    for url in [
+        "https://uptobox.com/abcdef123456",
+        "http://uptobox.com/abcdef123456",
+        "https://www.uptobox.com/abcdef123456",
+        "uptobox.com/abcdef123456",
+        "https://uptobox.com/abcdef123456/",
+        "https://uptobox.com/abcdef123456?aff_id=1",
+        "https://uptobox.com/abcdef123456#top",
+    ] {
+        assert_eq!(parse(url), ("abcdef123456".into(), None), "{url}");
+    }
+}
+
+#[test]
+fn mirrors() {
+    for url in [
+        "https://uptobox.fr/abcdef123456",
+        "https://uptobox.eu/abcdef123456",
+        "https://uptobox.link/abcdef123456",
+        "https://uptostream.fr/abcdef123456",
+        "https://uptostream.eu/abcdef123456",
+    ] {
+        assert_eq!(parse(url), ("abcdef123456".into(), None), "{url}");
+    }
+}
+
+#[test]
+fn uptostream_url() {
+    for url in [
+        "https://uptostream.com/abcdef123456",
+        "https://uptostream.com/iframe/abcdef123456",
+        "https://uptobox.com/iframe/abcdef123456",
+    ] {
+        assert_eq!(parse(url), ("abcdef123456".into(), None), "{url}");
+    }
+}
+
+#[test]
+fn code_with_password() {
+    assert_eq!(
+        parse("abcdef123456:secret"),
+        ("abcdef123456".into(), Some("secret".into()))
+    );
+    assert_eq!(
+        parse("abcdef123456:my:secret"),
+        ("abcdef123456".into(), Some("my:secret".into()))
+    );
+    assert_eq!(parse("abcdef123456:"), ("abcdef123456".into(), None));
+    assert_eq!(
+        FileCode::parse("abcdef123456")
+            .unwrap()
+            .with_password("secret")
+            .password(),
+        Some("secret")
+    );
+}
+
+#[test]
+fn rejected_inputs() {
+    for input in [
+        "",
+        "   ",
+        "abc-def",
+        "abc def",
+        ":secret",
+        "https://uptobox.com/",
+        "https://uptobox.com",
+        "https://uptostream.com/iframe/",
+        "https://example.com/abcdef123456",
+        "https://uptobox.community/abcdef123456",
+        "ftp://uptobox.com.evil/abcdef123456",
+    ] {
+        assert!(FileCode::parse(input).is_err(), "{input} should be invalid");
+    }
+}
+
+#[test]
+fn password_redacted_from_errors() {
+    assert_eq!(invalid("bad-code:secret"), "bad-code:[redacted]");
+    assert_eq!(invalid("bad-code:my:secret"), "bad-code:[redacted]");
+    assert_eq!(
+        invalid("https://example.com/bad:my:secret"),
+        "https://example.com/bad:[redacted]"
+    );
+    assert_eq!(invalid("bad-code"), "bad-code");
+    assert_eq!(
+        invalid("https://example.com/abc"),
+        "https://example.com/abc"
+    );
+}
+
+#[test]
+fn into_file_code() {
+    let expected = FileCode::parse("abcdef123456:secret").unwrap();
+    let owned = String::from("abcdef123456:secret");
+
+    assert_eq!("abcdef123456:secret".into_file_code().unwrap(), expected);
+    assert_eq!((&owned).into_file_code().unwrap(), expected);
+    assert_eq!(owned.into_file_code().unwrap(), expected);
+    assert_eq!((&expected).into_file_code().unwrap(), expected);
+    assert_eq!(expected.clone().into_file_code().unwrap(), expected);
+    assert!("bad-code".into_file_code().is_err());
+
+    let parsed: FileCode = "https://uptobox.com/abcdef123456".parse().unwrap();
+    assert_eq!(parsed.code(), "abcdef123456");
+}
+
+#[test]
+fn display_and_url() {
+    let file_code = FileCode::parse("abcdef123456:secret").unwrap();
+
+    assert_eq!(file_code.to_string(), "abcdef123456");
+    assert_eq!(file_code.url(), "https://uptobox.com/abcdef123456");
+    assert!(!format!("{file_code:?}").contains("secret"));
+}
+
+#[test]
+fn input_constructors() {
+    let file_code = FileCode::parse("abcdef123456:secret").unwrap();
+
+    // The password of the file code is kept
+    let get_download_url = GetDownloadUrl::new(&file_code).unwrap();
+    assert_eq!(
+        serde_json::to_value(&get_download_url).unwrap(),
+        json!({ "file_code": "abcdef123456", "password": "secret" })
+    );
+    assert_eq!(
+        serde_json::to_value(GetDownloadUrl::new("https://uptobox.com/abcdef123456").unwrap())
+            .unwrap(),
+        json!({ "file_code": "abcdef123456", "password": "" })
+    );
+
+    let mut update_file = UpdateFile::new(file_code).unwrap();
+    update_file.name("new name");
+    assert_eq!(
+        serde_json::to_value(&update_file).unwrap(),
+        json!({ "file_code": "abcdef123456", "new_name": "new name" })
+    );
+
+    assert!(GetDownloadUrl::new("bad-code").is_err());
+    assert!(UpdateFile::new("bad-code").is_err());
+}
@@ -0,0 +1,91 @@
+use reqwest::Client;
+use std::sync::OnceLock;
+
+use rs_uptobox::{GetFiles, OrderBy, OrderDir, SearchField};
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Query string sent to the api for the input
+fn query(get_files: &GetFiles) -> String {
+    let params = serde_json::to_value(get_files).unwrap();
+    let request = CLIENT
+        .get_or_init(Client::new)
+        .get("https://uptobox.com/api/user/files")
+        .query(&params)
+        .build()
+        .unwrap();
+
+    request.url().query().unwrap_or_default().to_string()
+}
+
+fn order_bys() -> [(OrderBy, &'static str); 5] {
+    [
+        (OrderBy::FileName, "file_name"),
+        (OrderBy::FileDate, "file_date"),
+        (OrderBy::FileSize, "file_size"),
+        (OrderBy::Transcoded, "transcoded"),
+        (OrderBy::FileDownloads, "file_downloads"),
+    ]
+}
+
+fn order_dirs() -> [(OrderDir, &'static str); 2] {
+    [(OrderDir::Asc, "ASC"), (OrderDir::Desc, "DESC")]
+}
+
+fn search_fields() -> [Option<(SearchField, &'static str)>; 3] {
+    [
+        None,
+        Some((SearchField::FileName, "file_name")),
+        Some((SearchField::FileDescr, "file_descr")),
+    ]
+}
+
+#[test]
+fn default() {
+    assert_eq!(
+        query(&GetFiles::default()),
+        "dir=ASC&limit=100&offset=0&orderBy=file_name&path=%2F%2F%2F"
+    );
+}
+
+#[test]
+fn new() {
+    assert_eq!(
+        query(&GetFiles::new("//dev")),
+        "dir=ASC&limit=100&offset=0&orderBy=file_name&path=%2F%2Fdev"
+    );
+}
+
+#[test]
+fn every_combination() {
+    for (limit, offset) in [(100, 0), (50, 2)] {
+        for (order_by, order_by_query) in order_bys() {
+            for (order_dir, order_dir_query) in order_dirs() {
+                for search_field in search_fields() {
+                    let mut get_files = GetFiles::new("//dev");
+                    get_files
+                        .limit(limit)
+                        .offset(offset)
+                        .order_by(order_by)
+                        .order_dir(order_dir);
+
+                    let search_query = match search_field {
+                        Some((search_field, search_field_query)) => {
+                            get_files.search(search_field, "my file");
+                            format!("&search=my+file&searchField={search_field_query}")
+                        }
+                        None => String::new(),
+                    };
+
+                    assert_eq!(
+                        query(&get_files),
+                        format!(
+                            "dir={order_dir_query}&limit={limit}&offset={offset}\
+                             &orderBy={order_by_query}&path=%2F%2Fdev{search_query}"
+                        )
+                    );
+                }
+            }
+        }
+    }
+}
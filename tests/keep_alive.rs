@@ -0,0 +1,71 @@
+#![cfg(feature = "chrono")]
+
+mod common;
+
+use common::{date, file};
+use rs_uptobox::KeepAlive;
+
+#[test]
+fn default_margin_boundary() {
+    // Purged after 30 days, refreshed from 7 days before: the limit is 23 days ago
+    let mut keep_alive = KeepAlive::new(30);
+    keep_alive.now(date("2023-03-31 12:00:00"));
+
+    assert!(keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2023-01-01 00:00:00")
+            .last_download("2023-03-08 12:00:00")
+            .build()
+    ));
+    assert!(!keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2023-01-01 00:00:00")
+            .last_download("2023-03-08 12:00:01")
+            .build()
+    ));
+}
+
+#[test]
+fn custom_margin_boundary() {
+    let mut keep_alive = KeepAlive::new(30);
+    keep_alive.margin_days(0).now(date("2023-03-31 12:00:00"));
+
+    assert!(keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2023-01-01 00:00:00")
+            .last_download("2023-03-01 12:00:00")
+            .build()
+    ));
+    assert!(!keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2023-01-01 00:00:00")
+            .last_download("2023-03-01 12:00:01")
+            .build()
+    ));
+}
+
+#[test]
+fn never_downloaded() {
+    let mut keep_alive = KeepAlive::new(30);
+    keep_alive.now(date("2023-03-31 12:00:00"));
+
+    // The files never downloaded are checked against their creation date
+    assert!(keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2023-03-08 12:00:00")
+            .last_download("0000-00-00 00:00:00")
+            .build()
+    ));
+    assert!(!keep_alive.needs_refresh(&file("abcdef123456").created("2023-03-08 12:00:01").build()));
+
+    // A recent download wins over an old creation date
+    assert!(!keep_alive.needs_refresh(
+        &file("abcdef123456")
+            .created("2022-01-01 00:00:00")
+            .last_download("2023-03-30 00:00:00")
+            .build()
+    ));
+
+    // Without any date, the file is not refreshed
+    assert!(!keep_alive.needs_refresh(&file("abcdef123456").build()));
+}
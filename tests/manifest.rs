@@ -0,0 +1,314 @@
+mod common;
+
+use common::file;
+use rs_uptobox::{
+    FileVisibility, Manifest, ManifestFile, ManifestFolder, MetadataChange, RestoreAction,
+    UptoboxPath, MANIFEST_VERSION,
+};
+
+fn folder(path: &str, fld_id: usize) -> ManifestFolder {
+    ManifestFolder {
+        path: UptoboxPath::parse(path),
+        fld_id,
+        hash: format!("hash{fld_id}"),
+    }
+}
+
+fn manifest(folders: Vec<ManifestFolder>, files: Vec<ManifestFile>) -> Manifest {
+    Manifest {
+        version: MANIFEST_VERSION,
+        taken_at: 1000,
+        root: UptoboxPath::root(),
+        folders,
+        files,
+    }
+}
+
+fn paths(folders: &[ManifestFolder]) -> Vec<String> {
+    folders.iter().map(|f| f.path.to_string()).collect()
+}
+
+fn codes(files: &[ManifestFile]) -> Vec<&str> {
+    files.iter().map(|f| f.file.file_code.as_str()).collect()
+}
+
+#[test]
+fn diff_folders() {
+    let older = manifest(
+        vec![folder("//", 0), folder("//old", 1), folder("//kept", 2)],
+        vec![],
+    );
+    let newer = manifest(
+        vec![folder("//", 0), folder("//kept", 3), folder("//new", 4)],
+        vec![],
+    );
+    let diff = older.diff(&newer);
+
+    assert_eq!(paths(&diff.added_folders), ["//new"]);
+    assert_eq!(paths(&diff.removed_folders), ["//old"]);
+    assert!(diff.added.is_empty() && diff.removed.is_empty());
+}
+
+#[test]
+fn diff_files() {
+    let older = manifest(
+        vec![folder("//", 0)],
+        vec![
+            file("unchanged000").manifest_file("//"),
+            file("removed00000").name("b.txt").manifest_file("//"),
+            file("renamed00000").name("c.txt").manifest_file("//"),
+            file("moved0000000").name("d.txt").manifest_file("//"),
+            file("both00000000").name("e.txt").manifest_file("//"),
+        ],
+    );
+    let newer = manifest(
+        vec![folder("//", 0)],
+        vec![
+            file("unchanged000").manifest_file("//"),
+            file("renamed00000").name("c2.txt").manifest_file("//"),
+            file("moved0000000").name("d.txt").manifest_file("//dev"),
+            file("both00000000").name("e2.txt").manifest_file("//dev"),
+            file("added0000000").name("f.txt").manifest_file("//"),
+        ],
+    );
+    let diff = older.diff(&newer);
+
+    assert_eq!(codes(&diff.added), ["added0000000"]);
+    assert_eq!(codes(&diff.removed), ["removed00000"]);
+
+    let renamed: Vec<(&str, &str)> = diff
+        .renamed
+        .iter()
+        .map(|c| {
+            (
+                c.before.file.file_name.as_str(),
+                c.after.file.file_name.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(renamed, [("c.txt", "c2.txt"), ("e.txt", "e2.txt")]);
+
+    let moved: Vec<(String, String)> = diff
+        .moved
+        .iter()
+        .map(|c| (c.before.path.to_string(), c.after.path.to_string()))
+        .collect();
+    assert_eq!(
+        moved,
+        [("//".into(), "//dev".into()), ("//".into(), "//dev".into())]
+    );
+    assert!(diff.metadata_changed.is_empty());
+    assert!(!diff.is_empty());
+}
+
+#[test]
+fn diff_metadata() {
+    let original = file("abcdef123456").manifest_file("//");
+    let older = manifest(vec![], vec![original.clone()]);
+
+    let mut descr = original.clone();
+    descr.file.file_descr = "description".into();
+    let mut password = original.clone();
+    password.file.file_password = Some("secret".into());
+    let mut public = original.clone();
+    public.file.file_public = FileVisibility::Public;
+    let mut size = original.clone();
+    size.file.file_size = 11;
+    let mut downloads = original.clone();
+    downloads.file.file_downloads = 100;
+
+    for changed in [descr, password, public, size] {
+        let diff = older.diff(&manifest(vec![], vec![changed.clone()]));
+        assert_eq!(diff.metadata_changed.len(), 1, "{changed:?}");
+        assert_eq!(diff.metadata_changed[0].before, original);
+        assert_eq!(diff.metadata_changed[0].after, changed);
+        assert!(diff.renamed.is_empty() && diff.moved.is_empty());
+    }
+
+    // The statistics are not metadata
+    assert!(older.diff(&manifest(vec![], vec![downloads])).is_empty());
+    assert!(older.diff(&older).is_empty());
+}
+
+#[test]
+fn json_round_trip() {
+    let manifest = manifest(
+        vec![folder("//", 0), folder("//dev", 1)],
+        vec![file("abcdef123456").manifest_file("//dev")],
+    );
+    let json = manifest.to_json().unwrap();
+
+    assert_eq!(Manifest::from_json(&json).unwrap(), manifest);
+}
+
+#[test]
+fn newer_version_rejected() {
+    let mut newer = manifest(vec![], vec![]);
+    newer.version = MANIFEST_VERSION + 1;
+    let error = Manifest::from_json(&newer.to_json().unwrap()).unwrap_err();
+    assert!(error.to_string().contains(&format!(
+        "unsupported manifest version: {}",
+        MANIFEST_VERSION + 1
+    )));
+
+    let mut older = manifest(vec![], vec![]);
+    older.version = 0;
+    assert!(Manifest::from_json(&older.to_json().unwrap()).is_ok());
+}
+
+#[test]
+fn restore_creates_the_parents_first() {
+    let target = manifest(
+        vec![
+            folder("//a/b/c", 3),
+            folder("//", 0),
+            folder("//a/b", 2),
+            folder("//d", 4),
+            folder("//a", 1),
+        ],
+        vec![],
+    );
+    let current = manifest(vec![folder("//", 0), folder("//a", 1)], vec![]);
+    let plan = target.restore_plan(&current);
+
+    assert_eq!(
+        plan.actions,
+        [
+            RestoreAction::CreateFolder {
+                path: UptoboxPath::parse("//d")
+            },
+            RestoreAction::CreateFolder {
+                path: UptoboxPath::parse("//a/b")
+            },
+            RestoreAction::CreateFolder {
+                path: UptoboxPath::parse("//a/b/c")
+            },
+        ]
+    );
+    assert!(target.restore_plan(&target).is_empty());
+}
+
+#[test]
+fn restore_moves() {
+    let target = manifest(
+        vec![folder("//", 0), folder("//dev", 1)],
+        vec![
+            file("moved0000000").manifest_file("//dev"),
+            file("kept00000000").name("b.txt").manifest_file("//"),
+        ],
+    );
+    let current = manifest(
+        vec![folder("//", 0), folder("//dev", 1), folder("//other", 2)],
+        vec![
+            file("moved0000000").manifest_file("//other"),
+            file("kept00000000").name("b.txt").manifest_file("//"),
+        ],
+    );
+    let plan = target.restore_plan(&current);
+
+    assert_eq!(
+        plan.actions,
+        [RestoreAction::MoveFile {
+            file_code: "moved0000000".into(),
+            from: UptoboxPath::parse("//other"),
+            to: UptoboxPath::parse("//dev"),
+        }]
+    );
+    assert!(plan.missing_files.is_empty());
+}
+
+#[test]
+fn restore_metadata() {
+    let mut target = file("abcdef123456").manifest_file("//");
+    target.file.file_descr = "description".into();
+    target.file.file_password = Some("secret".into());
+    target.file.file_public = FileVisibility::Public;
+    let mut current = file("abcdef123456").name("renamed.txt").manifest_file("//");
+
+    let update = |target: &ManifestFile, current: &ManifestFile| {
+        manifest(vec![], vec![target.clone()])
+            .restore_plan(&manifest(vec![], vec![current.clone()]))
+            .actions
+    };
+
+    assert_eq!(
+        update(&target, &current),
+        [RestoreAction::UpdateFile {
+            file_code: "abcdef123456".into(),
+            changes: vec![
+                MetadataChange::Name("a.txt".into()),
+                MetadataChange::Description("description".into()),
+                MetadataChange::Password("secret".into()),
+                MetadataChange::Public(true),
+            ],
+        }]
+    );
+
+    // A password added since the manifest is removed
+    current = target.clone();
+    current.file.file_password = Some("other".into());
+    target.file.file_password = None;
+    assert_eq!(
+        update(&target, &current),
+        [RestoreAction::UpdateFile {
+            file_code: "abcdef123456".into(),
+            changes: vec![MetadataChange::Password(String::new())],
+        }]
+    );
+}
+
+#[test]
+fn restore_moves_before_updates() {
+    let mut target = file("abcdef123456").manifest_file("//dev");
+    target.file.file_public = FileVisibility::Public;
+    let current = file("abcdef123456").manifest_file("//");
+    let plan = manifest(vec![folder("//dev", 1)], vec![target])
+        .restore_plan(&manifest(vec![], vec![current]));
+
+    assert!(matches!(
+        plan.actions.as_slice(),
+        [
+            RestoreAction::CreateFolder { .. },
+            RestoreAction::MoveFile { .. },
+            RestoreAction::UpdateFile { .. }
+        ]
+    ));
+}
+
+#[test]
+fn restore_missing_files() {
+    let missing = file("missing00000").manifest_file("//dev");
+    let target = manifest(
+        vec![],
+        vec![
+            missing.clone(),
+            file("kept00000000").name("b.txt").manifest_file("//"),
+        ],
+    );
+    let current = manifest(
+        vec![],
+        vec![file("kept00000000").name("b.txt").manifest_file("//")],
+    );
+    let plan = target.restore_plan(&current);
+
+    assert!(plan.is_empty());
+    assert_eq!(plan.missing_files, [missing]);
+    assert_eq!(plan.to_string(), "missing //dev/a.txt (missing00000)\n");
+}
+
+#[test]
+fn restore_plan_redacts_the_passwords() {
+    let mut target = file("abcdef123456").manifest_file("//");
+    target.file.file_password = Some("secret".into());
+    let mut missing = file("missing00000").name("b.txt").manifest_file("//");
+    missing.file.file_password = Some("hunter2".into());
+    let plan = manifest(vec![], vec![target, missing]).restore_plan(&manifest(
+        vec![],
+        vec![file("abcdef123456").manifest_file("//")],
+    ));
+
+    let debug = format!("{plan:?}");
+    assert!(!debug.contains("secret") && !debug.contains("hunter2"));
+    assert!(debug.contains("[redacted]"));
+    assert!(!plan.to_string().contains("secret"));
+}
@@ -0,0 +1,268 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+
+use rs_uptobox::{
+    GetAccountPaymentsResponse, GetAccountResponse, GetDownloadUrlResponse, GetFilesFiles,
+    GetFilesFromPublicFolderResponse, GetFilesInformationsResponse, GetFilesResponse,
+    GetUploadUrlResponse, RawResponse,
+};
+
+/// Deserialize, serialize and deserialize again, and check that nothing was lost
+fn round_trip<T>(json: Value) -> Value
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let first: T = serde_json::from_value(json).unwrap();
+    let serialized = serde_json::to_value(&first).unwrap();
+    let second: T = serde_json::from_value(serialized.clone()).unwrap();
+
+    assert_eq!(first, second);
+    serialized
+}
+
+#[test]
+fn get_account() {
+    let serialized = round_trip::<GetAccountResponse>(json!({
+        "premium": 1,
+        "login": "login",
+        "email": "email@example.com",
+        "point": 12.5,
+        "premium_expire": "2023-01-31 12:00:00",
+        "securityLock": 0,
+        "directDownload": 1,
+        "sslDownload": 0,
+        "token": "token"
+    }));
+
+    for field in [
+        "premium_expire",
+        "securityLock",
+        "directDownload",
+        "sslDownload",
+    ] {
+        assert!(serialized.get(field).is_some(), "missing field {field}");
+    }
+}
+
+#[test]
+fn get_account_payments() {
+    let serialized = round_trip::<Vec<GetAccountPaymentsResponse>>(json!([
+        {
+            "created": "2023-01-31 12:00:00",
+            "status": "paid",
+            "amount": "10.00",
+            "days": 30,
+            "type": "paypal"
+        },
+        {
+            "created": "2022-12-31 12:00:00",
+            "status": "something new",
+            "amount": "10.00",
+            "days": 30,
+            "type": "something new"
+        },
+        {
+            "created": "2022-11-30 12:00:00",
+            "status": "success",
+            "amount": "10.00",
+            "days": 30,
+            "type": "cb"
+        },
+        {
+            "created": "2022-10-31 12:00:00",
+            "status": "PAID",
+            "amount": "10.00",
+            "days": 30,
+            "type": "Card"
+        }
+    ]));
+
+    assert_eq!(serialized[0]["status"], "paid");
+    assert_eq!(serialized[0]["type"], "paypal");
+    assert_eq!(serialized[1]["status"], "something new");
+
+    // The values not mapped to a variant are serialized back unchanged
+    assert_eq!(serialized[2]["status"], "success");
+    assert_eq!(serialized[2]["type"], "cb");
+    assert_eq!(serialized[3]["status"], "PAID");
+    assert_eq!(serialized[3]["type"], "Card");
+}
+
+#[test]
+fn get_download_url() {
+    let serialized = round_trip::<GetDownloadUrlResponse>(json!({
+        "dlLink": "https://www.example.com/dl/abcdef123456"
+    }));
+    assert!(serialized.get("dlLink").is_some());
+
+    let serialized = round_trip::<GetDownloadUrlResponse>(json!({
+        "waiting": 30,
+        "waitingToken": "token"
+    }));
+    assert!(serialized.get("waitingToken").is_some());
+}
+
+#[test]
+fn get_files() {
+    let serialized = round_trip::<GetFilesResponse>(json!({
+        "currentFolder": {
+            "fileCount": 2,
+            "fld_id": 1,
+            "fld_parent_id": null,
+            "hash": "hash",
+            "totalFileSize": 300
+        },
+        "folders": [
+            { "fld_id": 2, "fld_name": "//dev", "hash": "hash", "name": "dev" }
+        ],
+        "files": [
+            {
+                "file_code": "abcdef123456",
+                "file_created": "2023-01-31 12:00:00",
+                "file_descr": "",
+                "file_downloads": 3,
+                "file_last_download": "0000-00-00 00:00:00",
+                "file_name": "file.mkv",
+                "file_password": "",
+                "file_public": 0,
+                "file_size": 100,
+                "id": 1,
+                "last_stream": "0000-00-00 00:00:00",
+                "nb_stream": 0,
+                "transcoded": null
+            },
+            {
+                "file_code": "ghijkl123456",
+                "file_created": "2023-01-31 12:00:00",
+                "file_descr": "description",
+                "file_downloads": 0,
+                "file_last_download": "2023-02-01 12:00:00",
+                "file_name": "file.mp4",
+                "file_password": "password",
+                "file_public": 1,
+                "file_size": 200,
+                "id": null,
+                "last_stream": "2023-02-01 12:00:00",
+                "nb_stream": 4,
+                "transcoded": 1
+            }
+        ],
+        "pageCount": 1,
+        "totalFileCount": 2,
+        "totalFileSize": 300
+    }));
+
+    for field in [
+        "currentFolder",
+        "pageCount",
+        "totalFileCount",
+        "totalFileSize",
+    ] {
+        assert!(serialized.get(field).is_some(), "missing field {field}");
+    }
+    for field in ["fileCount", "totalFileSize", "fld_name", "name"] {
+        assert!(
+            serialized["currentFolder"].get(field).is_some(),
+            "missing field {field}"
+        );
+    }
+    assert_eq!(serialized["files"][0]["file_password"], "");
+    assert_eq!(serialized["files"][1]["file_public"], 1);
+    assert_eq!(serialized["files"][1]["transcoded"], 1);
+}
+
+#[test]
+fn get_files_from_public_folder() {
+    round_trip::<Vec<GetFilesFromPublicFolderResponse>>(json!([
+        {
+            "file_name": "file.mkv",
+            "file_date_inserted": "2023-01-31 12:00:00",
+            "file_code": "abcdef123456"
+        }
+    ]));
+}
+
+#[test]
+fn get_files_informations() {
+    round_trip::<Vec<GetFilesInformationsResponse>>(json!([
+        {
+            "file_code": "abcdef123456",
+            "file_name": "file.mkv",
+            "file_size": 100,
+            "available_uts": true,
+            "need_premium": false,
+            "error": null
+        },
+        {
+            "file_code": "ghijkl123456",
+            "file_name": "",
+            "file_size": 0,
+            "available_uts": false,
+            "need_premium": false,
+            "error": { "code": 28, "message": "File not found" }
+        }
+    ]));
+}
+
+#[test]
+fn get_upload_url() {
+    let serialized = round_trip::<GetUploadUrlResponse>(json!({
+        "uploadLink": "//www.example.com/upload",
+        "maxUpload": "200 GB",
+        "newField": { "unknown": true }
+    }));
+
+    assert!(serialized.get("uploadLink").is_some());
+    assert!(serialized.get("maxUpload").is_some());
+    assert_eq!(serialized["newField"], json!({ "unknown": true }));
+}
+
+#[test]
+fn raw() {
+    let serialized = round_trip::<RawResponse>(json!({
+        "statusCode": 0,
+        "message": "Success",
+        "data": { "anything": [1, 2, 3] }
+    }));
+
+    assert!(serialized.get("statusCode").is_some());
+}
+
+#[test]
+fn dates_are_kept_raw() {
+    let file: GetFilesFiles = serde_json::from_value(json!({
+        "file_code": "abcdef123456",
+        "file_created": "2023-01-31 12:00:00",
+        "file_descr": "",
+        "file_downloads": 0,
+        "file_last_download": "0000-00-00 00:00:00",
+        "file_name": "file.mkv",
+        "file_password": "",
+        "file_public": 0,
+        "file_size": 100,
+        "id": 1,
+        "last_stream": "not a date",
+        "nb_stream": 0,
+        "transcoded": null
+    }))
+    .unwrap();
+
+    // The fields are the same with or without the chrono feature
+    let created: &String = &file.file_created;
+    assert_eq!(created, "2023-01-31 12:00:00");
+    assert_eq!(
+        serde_json::to_value(&file).unwrap()["last_stream"],
+        "not a date"
+    );
+
+    #[cfg(feature = "chrono")]
+    {
+        let created =
+            chrono::NaiveDate::from_ymd_opt(2023, 1, 31).and_then(|d| d.and_hms_opt(12, 0, 0));
+        assert_eq!(file.file_created_at(), created);
+        assert_eq!(file.file_last_download_at(), None);
+        assert_eq!(file.last_stream_at(), None);
+    }
+}
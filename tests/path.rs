@@ -0,0 +1,114 @@
+use serde_json::Map;
+
+use rs_uptobox::{GetFilesFolders, UptoboxPath};
+
+fn normalize(path: &str) -> String {
+    UptoboxPath::parse(path).to_string()
+}
+
+fn folder(fld_name: &str, name: &str) -> GetFilesFolders {
+    GetFilesFolders {
+        fld_id: 1,
+        fld_name: fld_name.into(),
+        hash: "hash".into(),
+        name: name.into(),
+        extra: Map::new(),
+    }
+}
+
+#[test]
+fn duplicate_and_trailing_slashes() {
+    for path in [
+        "//dev/builds",
+        "dev/builds",
+        "///dev//builds",
+        "//dev/builds/",
+        "//dev///builds//",
+    ] {
+        assert_eq!(normalize(path), "//dev/builds", "{path}");
+    }
+}
+
+#[test]
+fn dots() {
+    assert_eq!(normalize("//dev/./builds/."), "//dev/builds");
+    assert_eq!(normalize("//dev/builds/../docs"), "//dev/docs");
+    assert_eq!(normalize("//dev/builds/.."), "//dev");
+    assert_eq!(
+        UptoboxPath::parse("//dev").join("../docs/./v1"),
+        UptoboxPath::parse("//docs/v1")
+    );
+}
+
+#[test]
+fn dots_above_the_root() {
+    assert_eq!(normalize("//.."), "//");
+    assert_eq!(normalize("//../../dev"), "//dev");
+    assert_eq!(normalize("//dev/../../builds"), "//builds");
+    assert!(UptoboxPath::parse("//dev/../..").is_root());
+}
+
+#[test]
+fn root_forms() {
+    for path in ["", "/", "//", "///", ".", "//./"] {
+        let root = UptoboxPath::parse(path);
+        assert!(root.is_root(), "{path}");
+        assert_eq!(root, UptoboxPath::root());
+        assert_eq!(root.to_listing_path(), "///");
+        assert_eq!(root.to_folder_path(), "//");
+        assert_eq!(root.to_string(), "//");
+    }
+
+    let path = UptoboxPath::parse("//dev");
+    assert_eq!(path.to_listing_path(), "//dev");
+    assert_eq!(path.to_folder_path(), "//dev");
+}
+
+#[test]
+fn parent_and_file_name() {
+    let path = UptoboxPath::parse("//dev/builds");
+
+    assert_eq!(path.file_name(), Some("builds"));
+    assert_eq!(path.depth(), 2);
+    assert_eq!(path.parent(), Some(UptoboxPath::parse("//dev")));
+    assert_eq!(
+        path.parent().and_then(|p| p.parent()),
+        Some(UptoboxPath::root())
+    );
+    assert_eq!(UptoboxPath::root().parent(), None);
+    assert_eq!(UptoboxPath::root().file_name(), None);
+    assert!(path.starts_with(&UptoboxPath::parse("//dev")));
+    assert!(path.starts_with(&UptoboxPath::root()));
+    assert!(!UptoboxPath::parse("//devel").starts_with(&UptoboxPath::parse("//dev")));
+}
+
+#[test]
+fn folder_path() {
+    let parent = UptoboxPath::parse("//dev");
+
+    assert_eq!(
+        folder("//dev/builds", "builds").path(&parent),
+        UptoboxPath::parse("//dev/builds")
+    );
+    // The full path of the api wins over the folder listed
+    assert_eq!(
+        folder("//other/builds/", "builds").path(&parent),
+        UptoboxPath::parse("//other/builds")
+    );
+    assert_eq!(
+        folder("builds", "builds").path(&parent),
+        UptoboxPath::parse("//dev/builds")
+    );
+    assert_eq!(
+        folder("", "builds").path(&UptoboxPath::root()),
+        UptoboxPath::parse("//builds")
+    );
+}
+
+#[test]
+fn serde() {
+    let path: UptoboxPath = serde_json::from_str(r#""//dev//builds/""#).unwrap();
+
+    assert_eq!(path, UptoboxPath::parse("//dev/builds"));
+    assert_eq!(serde_json::to_string(&path).unwrap(), r#""//dev/builds""#);
+}
@@ -0,0 +1,61 @@
+use serde_json::json;
+
+use rs_uptobox::{FileCode, GetAccountResponse, GetDownloadUrl, UpdateFile, Uptobox};
+
+const SECRET: &str = "s3cr3t";
+
+#[test]
+fn uptobox() {
+    let mut uptobox = Uptobox::new(SECRET);
+    uptobox.lenient(true);
+
+    let debug = format!("{uptobox:?}");
+    assert!(!debug.contains(SECRET), "{debug}");
+    assert!(debug.contains("[redacted]"));
+}
+
+#[test]
+fn get_download_url() {
+    let file_code = FileCode::parse(&format!("abcdef123456:{SECRET}")).unwrap();
+    let mut get_download_url = GetDownloadUrl::new(&file_code).unwrap();
+
+    assert!(!format!("{get_download_url:?}").contains(SECRET));
+    assert!(!format!("{file_code:?}").contains(SECRET));
+
+    get_download_url.password(format!("other-{SECRET}"));
+    let debug = format!("{get_download_url:?}");
+    assert!(!debug.contains(SECRET), "{debug}");
+    assert!(debug.contains("abcdef123456"));
+}
+
+#[test]
+fn update_file() {
+    let mut update_file = UpdateFile::new("abcdef123456").unwrap();
+    update_file.name("name").password(SECRET);
+
+    let debug = format!("{update_file:?}");
+    assert!(!debug.contains(SECRET), "{debug}");
+    assert!(debug.contains("name"));
+}
+
+#[test]
+fn get_account() {
+    let account: GetAccountResponse = serde_json::from_value(json!({
+        "premium": 1,
+        "login": "login",
+        "email": "email@example.com",
+        "point": 12.5,
+        "premium_expire": "2023-01-31 12:00:00",
+        "securityLock": 0,
+        "directDownload": 1,
+        "sslDownload": 0,
+        "token": SECRET
+    }))
+    .unwrap();
+
+    let debug = format!("{account:?}");
+    assert!(!debug.contains(SECRET), "{debug}");
+    assert!(debug.contains("login"));
+    // The token is still available to the caller
+    assert_eq!(account.token, SECRET);
+}
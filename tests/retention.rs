@@ -0,0 +1,112 @@
+#![cfg(feature = "chrono")]
+
+mod common;
+
+use chrono::Duration;
+
+use common::{date, file};
+use rs_uptobox::{RetentionPolicy, RetentionRule};
+
+const NOW: &str = "2023-03-31 12:00:00";
+
+#[test]
+fn inactive_boundary() {
+    let rule = RetentionRule::Inactive(Duration::days(30));
+    let now = date(NOW);
+
+    // Exactly 30 days ago matches, one second later does not
+    let on_limit = file("abcdef123456")
+        .created("2023-01-01 00:00:00")
+        .last_download("2023-03-01 12:00:00")
+        .downloads(1)
+        .build();
+    let after_limit = file("abcdef123456")
+        .created("2023-01-01 00:00:00")
+        .last_download("2023-03-01 12:00:01")
+        .downloads(1)
+        .build();
+    assert!(rule.matches(&on_limit, now));
+    assert!(!rule.matches(&after_limit, now));
+}
+
+#[test]
+fn inactive_uses_the_last_activity() {
+    let rule = RetentionRule::Inactive(Duration::days(30));
+    let now = date(NOW);
+
+    // A recent stream keeps an old download alive
+    let streamed = file("abcdef123456")
+        .created("2023-01-01 00:00:00")
+        .last_download("2023-01-02 00:00:00")
+        .last_stream("2023-03-30 00:00:00")
+        .downloads(1)
+        .build();
+    assert!(!rule.matches(&streamed, now));
+
+    // The files never used are checked against their creation date
+    let never_used = file("abcdef123456")
+        .created("2023-03-01 12:00:00")
+        .last_download("0000-00-00 00:00:00")
+        .build();
+    assert!(rule.matches(&never_used, now));
+    let recent = file("abcdef123456").created("2023-03-01 12:00:01").build();
+    assert!(!rule.matches(&recent, now));
+
+    // Without any date, the file is never stale
+    assert!(!rule.matches(&file("abcdef123456").build(), now));
+}
+
+#[test]
+fn never_downloaded_boundary() {
+    let rule = RetentionRule::NeverDownloaded(Duration::days(30));
+    let now = date(NOW);
+
+    assert!(rule.matches(
+        &file("abcdef123456").created("2023-03-01 12:00:00").build(),
+        now
+    ));
+    assert!(!rule.matches(
+        &file("abcdef123456").created("2023-03-01 12:00:01").build(),
+        now
+    ));
+    assert!(!rule.matches(
+        &file("abcdef123456")
+            .created("2023-01-01 00:00:00")
+            .downloads(1)
+            .build(),
+        now
+    ));
+    assert!(!rule.matches(&file("abcdef123456").build(), now));
+}
+
+#[test]
+fn policy_returns_the_first_rule() {
+    let mut policy = RetentionPolicy::new();
+    policy
+        .never_downloaded_days(30)
+        .inactive_days(10)
+        .now(date(NOW));
+
+    let old = file("abcdef123456").created("2023-01-01 00:00:00").build();
+    let used = file("abcdef123456")
+        .created("2023-01-01 00:00:00")
+        .last_download("2023-03-15 00:00:00")
+        .downloads(1)
+        .build();
+    let recent = file("abcdef123456")
+        .created("2023-01-01 00:00:00")
+        .last_download("2023-03-25 00:00:00")
+        .downloads(1)
+        .build();
+
+    assert_eq!(
+        policy.check(&old),
+        Some(RetentionRule::NeverDownloaded(Duration::days(30)))
+    );
+    assert_eq!(
+        policy.check(&used),
+        Some(RetentionRule::Inactive(Duration::days(10)))
+    );
+    assert_eq!(policy.check(&recent), None);
+    assert_eq!(RetentionPolicy::new().check(&old), None);
+}